@@ -0,0 +1,173 @@
+//! Attribute macro for defining owned iterators (see `owned_chars::owned_iterator`, which is
+//! re-exported with the `derive` feature)
+
+extern crate proc_macro;
+
+use proc_macro::{Spacing, TokenStream, TokenTree};
+
+/// Known str methods: name, inner iterator type, item type, item conversion, iterator traits
+const KNOWN: &[(&str, &str, &str, &str, &str)] = &[
+    ("chars", "::owned_chars::__private::str::Chars<'a>", "char", "x",
+     "DoubleEndedIterator, FusedIterator"),
+    ("char_indices", "::owned_chars::__private::str::CharIndices<'a>", "(usize, char)", "x",
+     "DoubleEndedIterator, FusedIterator"),
+    ("bytes", "::owned_chars::__private::str::Bytes<'a>", "u8", "x",
+     "DoubleEndedIterator, ExactSizeIterator, FusedIterator"),
+    ("encode_utf16", "::owned_chars::__private::str::EncodeUtf16<'a>", "u16", "x",
+     "FusedIterator"),
+    ("escape_debug", "::owned_chars::__private::str::EscapeDebug<'a>", "char", "x",
+     "FusedIterator"),
+    ("escape_default", "::owned_chars::__private::str::EscapeDefault<'a>", "char", "x",
+     "FusedIterator"),
+    ("escape_unicode", "::owned_chars::__private::str::EscapeUnicode<'a>", "char", "x",
+     "FusedIterator"),
+    ("lines", "::owned_chars::__private::str::Lines<'a>", "::owned_chars::__private::String", "x.into()",
+     "DoubleEndedIterator, FusedIterator"),
+    ("split_whitespace", "::owned_chars::__private::str::SplitWhitespace<'a>", "::owned_chars::__private::String", "x.into()",
+     "DoubleEndedIterator, FusedIterator"),
+    ("split_ascii_whitespace", "::owned_chars::__private::str::SplitAsciiWhitespace<'a>", "::owned_chars::__private::String", "x.into()",
+     "DoubleEndedIterator, FusedIterator"),
+];
+
+/// Turns a unit struct into an owned iterator (see `owned_chars::owned_iterator`)
+#[proc_macro_attribute]
+pub fn owned_iterator(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let code = match expand(attr, item) {
+        Ok(code) => code,
+        Err(msg) => format!("compile_error!({:?});", msg),
+    };
+    code.parse().expect("owned_iterator generated invalid tokens")
+}
+
+/// Specification of the iterator to generate
+struct Spec {
+    inner: String,
+    item: String,
+    new: String,
+    map: String,
+    traits: String,
+}
+
+fn expand(attr: TokenStream, item: TokenStream) -> Result<String, String> {
+    let (head, name) = parse_struct(item)?;
+    let spec = parse_spec(attr)?;
+    Ok(format!("::owned_chars::owned_iter! {{
+                    {head} struct {name}(for<'a> {inner}) -> {item} {{
+                        new: |s| {new},
+                        map: |x| {map},
+                        traits: [{traits}],
+                    }}
+                }}",
+               head = head, name = name,
+               inner = spec.inner, item = spec.item, new = spec.new, map = spec.map,
+               traits = spec.traits))
+}
+
+/// Splits `#[attrs] vis struct Name;` into the part before `struct` and the name
+fn parse_struct(item: TokenStream) -> Result<(String, String), String> {
+    const EXPECTED: &str = "#[owned_iterator] must be applied to a unit struct like `pub struct Name;`";
+
+    let mut tokens = item.into_iter();
+    let mut head = vec![];
+    loop {
+        match tokens.next() {
+            Some(TokenTree::Ident(ref i)) if i.to_string() == "struct" => break,
+            Some(tt) => head.push(tt),
+            None => return Err(EXPECTED.into()),
+        }
+    }
+    let name = match tokens.next() {
+        Some(TokenTree::Ident(name)) => name.to_string(),
+        _ => return Err(EXPECTED.into()),
+    };
+    match (tokens.next(), tokens.next()) {
+        (Some(TokenTree::Punct(ref p)), None) if p.as_char() == ';' => {}
+        _ => return Err(EXPECTED.into()),
+    }
+    Ok((head.into_iter().collect::<TokenStream>().to_string(), name))
+}
+
+/// Parses either `str::method` (for the methods in `KNOWN`) or a list of `key = value` settings
+fn parse_spec(attr: TokenStream) -> Result<Spec, String> {
+    let args = split_commas(attr);
+
+    if args.len() == 1 && !args[0].iter().any(is_eq) {
+        let path = tokens_to_string(&args[0]).replace(' ', "");
+        let method = path.trim_start_matches("str::");
+        return KNOWN.iter()
+            .find(|k| k.0 == method)
+            .map(|&(name, inner, item, map, traits)| Spec {
+                inner: inner.into(),
+                item: item.into(),
+                new: format!("s.{}()", name),
+                map: map.into(),
+                traits: traits.into(),
+            })
+            .ok_or_else(|| format!("`{}` is not a known str iterator method; specify the iterator \
+                                    with `inner = ..., item = ..., new = ...` instead", path));
+    }
+
+    let mut spec = Spec {
+        inner: String::new(),
+        item: String::new(),
+        new: String::new(),
+        map: "x".into(),
+        traits: String::new(),
+    };
+    for arg in args {
+        let eq = arg.iter().position(is_eq).ok_or("expected `key = value`")?;
+        let key = tokens_to_string(&arg[..eq]);
+        let value = tokens_to_string(&arg[eq + 1..]);
+        match &*key {
+            "inner" => spec.inner = value,
+            "item" => spec.item = value,
+            "new" => spec.new = format!("{}(s)", value),
+            "map" => spec.map = format!("{}(x)", value),
+            "traits" => spec.traits = value.trim_start_matches('[').trim_end_matches(']').into(),
+            _ => return Err(format!("unknown key `{}`", key)),
+        }
+    }
+    if spec.inner.is_empty() || spec.item.is_empty() || spec.new.is_empty() {
+        return Err("`inner`, `item` and `new` are required".into());
+    }
+    Ok(spec)
+}
+
+fn is_eq(tt: &TokenTree) -> bool {
+    match *tt {
+        TokenTree::Punct(ref p) => p.as_char() == '=' && p.spacing() == Spacing::Alone,
+        _ => false,
+    }
+}
+
+fn tokens_to_string(tokens: &[TokenTree]) -> String {
+    tokens.iter().cloned().collect::<TokenStream>().to_string()
+}
+
+/// Splits a token stream on commas that are not nested inside angle brackets
+fn split_commas(tokens: TokenStream) -> Vec<Vec<TokenTree>> {
+    let mut args = vec![vec![]];
+    let mut depth = 0usize;
+    let mut arrow = false;
+    for tt in tokens {
+        let mut next_arrow = false;
+        if let TokenTree::Punct(ref p) = tt {
+            match p.as_char() {
+                '<' => depth += 1,
+                '>' if !arrow => depth = depth.saturating_sub(1),
+                '-' => next_arrow = p.spacing() == Spacing::Joint,
+                ',' if depth == 0 => {
+                    args.push(vec![]);
+                    continue;
+                }
+                _ => {}
+            }
+        }
+        arrow = next_arrow;
+        args.last_mut().unwrap().push(tt);
+    }
+    if args.last().is_some_and(|a| a.is_empty()) {
+        args.pop();
+    }
+    args
+}
@@ -0,0 +1,141 @@
+//! Owned ASCII char iterator
+
+use alloc::string::String;
+use core::iter::{Iterator, DoubleEndedIterator, ExactSizeIterator, FusedIterator};
+use core::ops::Deref;
+use error::NotAscii;
+
+/// Iterator over the chars of a string that is known to be ASCII (the string is owned by the
+/// iterator)
+///
+/// Since every char is one byte, this knows its exact length and doesn't need to decode UTF-8.
+/// It is created by `OwnedAsciiChars::from_string` or `OwnedCharsExt::try_into_ascii_chars`,
+/// which check that the string is ASCII.
+///
+/// # Example
+///
+/// ```rust
+/// # use owned_chars::OwnedCharsExt;
+/// let mut chars = String::from("GET /").try_into_ascii_chars().unwrap();
+/// assert_eq!(chars.len(), 5);
+/// assert_eq!(chars.next(), Some('G'));
+/// assert_eq!(chars.next_back(), Some('/'));
+/// assert_eq!(chars.as_str(), "ET ");
+///
+/// let err = String::from("naïve").try_into_ascii_chars().unwrap_err();
+/// assert_eq!(err.offset(), 2);
+/// assert_eq!(err.into_string(), "naïve");
+/// ```
+#[derive(Debug, Clone)]
+pub struct OwnedAsciiChars {
+    s: String,
+    start: usize,
+    end: usize,
+}
+
+impl OwnedAsciiChars {
+    /// Create Self from a String, moving the String into Self
+    ///
+    /// Returns an error (which gives back the String) if the String is not ASCII.
+    pub fn from_string(s: String) -> Result<Self, NotAscii> {
+        // `is_ascii` checks a word at a time; the position is only needed for the error
+        if s.is_ascii() {
+            let end = s.len();
+            Ok(OwnedAsciiChars { s, start: 0, end })
+        } else {
+            let offset = s.bytes().position(|b| !b.is_ascii()).unwrap_or(0);
+            Err(NotAscii::new(s, offset))
+        }
+    }
+
+    /// Consume this struct and return the contained String
+    pub fn into_inner(self) -> String {
+        self.s
+    }
+
+    /// Returns a string slice of contained `String`
+    pub fn get_inner(&self) -> &str {
+        &self.s
+    }
+
+    /// Returns the part of the contained String that has not been yielded yet
+    pub fn as_str(&self) -> &str {
+        &self.s[self.start..self.end]
+    }
+
+    /// Returns the number of chars (and bytes) that have been consumed from the front
+    pub fn byte_offset(&self) -> usize {
+        self.start
+    }
+}
+
+impl Iterator for OwnedAsciiChars {
+    type Item = char;
+
+    fn next(&mut self) -> Option<char> {
+        if self.start < self.end {
+            self.start += 1;
+            Some(self.s.as_bytes()[self.start - 1] as char)
+        } else {
+            None
+        }
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.end - self.start;
+        (len, Some(len))
+    }
+    fn nth(&mut self, n: usize) -> Option<char> {
+        self.start += n.min(self.end - self.start);
+        self.next()
+    }
+}
+
+impl DoubleEndedIterator for OwnedAsciiChars {
+    fn next_back(&mut self) -> Option<char> {
+        if self.start < self.end {
+            self.end -= 1;
+            Some(self.s.as_bytes()[self.end] as char)
+        } else {
+            None
+        }
+    }
+}
+
+iter_traits!(OwnedAsciiChars, char, [ExactSizeIterator, FusedIterator]);
+
+/// Iterator adapter over an owned iterator whose remaining input is known to be ASCII
+///
+/// Created by the `check_ascii` method on the owned iterators, which only returns it if the check
+/// succeeds. Since every char is one byte, `size_hint` is exact (so this is an
+/// `ExactSizeIterator`) and `count` takes constant time. The wrapped iterator's accessors remain
+/// available through `Deref`.
+///
+/// # Example
+///
+/// ```rust
+/// # use owned_chars::OwnedCharsExt;
+/// let mut chars = String::from("plain").into_char_indices().check_ascii().unwrap();
+/// assert_eq!(chars.len(), 5);
+/// assert_eq!(chars.next(), Some((0, 'p')));
+/// assert_eq!(chars.char_to_byte_offset(2), Some(3));
+/// assert_eq!(chars.into_inner().as_str(), "lain");
+/// ```
+#[derive(Debug, Clone)]
+pub struct KnownAscii<I> {
+    pub(crate) iter: I,
+}
+
+impl<I> KnownAscii<I> {
+    /// Consume the adapter and return the wrapped iterator
+    pub fn into_inner(self) -> I {
+        self.iter
+    }
+}
+
+impl<I> Deref for KnownAscii<I> {
+    type Target = I;
+
+    fn deref(&self) -> &I {
+        &self.iter
+    }
+}
@@ -0,0 +1,111 @@
+//! Owned iterator over the UTF-8 encoding of each char
+
+use alloc::borrow::ToOwned;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::iter::{Iterator, DoubleEndedIterator, FusedIterator};
+#[cfg(feature = "lending")]
+use lending::LendingIterator;
+
+/// Iterator over the UTF-8 encoding of each char of a string (the string is owned by the iterator)
+///
+/// `next_bytes` and `next_back_bytes` borrow each encoding from the contained String, so an
+/// encoder or escaper can write the bytes out without re-encoding the char into a temporary
+/// buffer. As an `Iterator`, each encoding is returned as a new Vec.
+///
+/// # Example
+///
+/// ```rust
+/// # use owned_chars::OwnedCharBytes;
+/// let mut bytes = OwnedCharBytes::from_string(String::from("aé€"));
+/// assert_eq!(bytes.next_bytes(), Some(&b"a"[..]));
+/// assert_eq!(bytes.next_back_bytes(), Some(&b"\xe2\x82\xac"[..]));
+/// assert_eq!(bytes.as_str(), "é");
+/// assert_eq!(bytes.next(), Some(vec![0xc3, 0xa9]));
+/// assert_eq!(bytes.next(), None);
+/// ```
+#[derive(Debug, Clone)]
+pub struct OwnedCharBytes {
+    s: String,
+    start: usize,
+    end: usize,
+}
+
+impl OwnedCharBytes {
+    /// Create Self from a String, moving the String into Self
+    pub fn from_string(s: String) -> Self {
+        let end = s.len();
+        OwnedCharBytes { s, start: 0, end }
+    }
+
+    /// Consume this struct and return the contained String
+    pub fn into_inner(self) -> String {
+        self.s
+    }
+
+    /// Returns a string slice of contained `String`
+    pub fn get_inner(&self) -> &str {
+        &self.s
+    }
+
+    /// Returns the part of the contained String that has not been yielded yet
+    pub fn as_str(&self) -> &str {
+        &self.s[self.start..self.end]
+    }
+
+    /// Returns the byte position of the next char
+    pub fn byte_offset(&self) -> usize {
+        self.start
+    }
+
+    /// Like `next`, but borrows the encoding from the contained String instead of allocating
+    pub fn next_bytes(&mut self) -> Option<&[u8]> {
+        let len = self.as_str().chars().next()?.len_utf8();
+        let char_start = self.start;
+        self.start += len;
+        Some(&self.s.as_bytes()[char_start..self.start])
+    }
+
+    /// Like `next_back`, but borrows the encoding from the contained String instead of allocating
+    pub fn next_back_bytes(&mut self) -> Option<&[u8]> {
+        let len = self.as_str().chars().next_back()?.len_utf8();
+        let char_end = self.end;
+        self.end -= len;
+        Some(&self.s.as_bytes()[self.end..char_end])
+    }
+}
+
+impl Iterator for OwnedCharBytes {
+    type Item = Vec<u8>;
+
+    fn next(&mut self) -> Option<Vec<u8>> {
+        self.next_bytes().map(<[u8]>::to_owned)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        // same bounds as `Chars`: a char is 1 to 4 bytes long
+        let len = self.end - self.start;
+        (len.div_ceil(4), Some(len))
+    }
+
+    fn count(self) -> usize {
+        self.as_str().chars().count()
+    }
+}
+
+impl DoubleEndedIterator for OwnedCharBytes {
+    fn next_back(&mut self) -> Option<Vec<u8>> {
+        self.next_back_bytes().map(<[u8]>::to_owned)
+    }
+}
+
+iter_traits!(OwnedCharBytes, Vec<u8>, [FusedIterator]);
+
+#[cfg(feature = "lending")]
+impl LendingIterator for OwnedCharBytes {
+    type Item<'a> = &'a [u8];
+
+    fn next(&mut self) -> Option<&[u8]> {
+        self.next_bytes()
+    }
+}
@@ -0,0 +1,151 @@
+//! Incremental char decoder fed with chunks of bytes
+
+use alloc::vec::Vec;
+use core::iter::Iterator;
+use core::str;
+use error::InvalidUtf8;
+
+/// Decoder that is fed chunks of UTF-8 as they arrive (for example from a socket) and yields the
+/// chars that are complete so far
+///
+/// A multi-byte char split across chunks is buffered until the rest of it is pushed. Invalid
+/// sequences are reported as errors, with positions counted from the start of the whole stream,
+/// following the same rules as `Utf8Chars`. No I/O is done: the caller pushes bytes with `push` or
+/// `push_vec` and pulls chars with `next`.
+///
+/// Because more input can arrive later, `next` returning None only means that no complete char is
+/// buffered right now, so this iterator is not fused. Once `finish` is called, an incomplete
+/// sequence left at the end is reported as an error instead of being held back.
+///
+/// # Example
+///
+/// ```rust
+/// # use owned_chars::ChunkedChars;
+/// let mut chars = ChunkedChars::new();
+/// chars.push(b"caf\xc3");
+/// assert_eq!(chars.by_ref().collect::<Result<String, _>>().unwrap(), "caf");
+/// assert_eq!(chars.pending(), b"\xc3");
+///
+/// chars.push(b"\xa9 \xe2\x82");
+/// assert_eq!(chars.by_ref().collect::<Result<String, _>>().unwrap(), "é ");
+///
+/// chars.finish();
+/// let err = chars.next().unwrap().unwrap_err();
+/// assert_eq!((err.offset(), err.error_len()), (6, 2));
+/// assert_eq!(chars.next(), None);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ChunkedChars {
+    buf: Vec<u8>,
+    pos: usize,
+    /// number of bytes dropped from the front of `buf`
+    base: usize,
+    finished: bool,
+}
+
+impl ChunkedChars {
+    /// Create an empty decoder
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append a chunk of input
+    ///
+    /// # Panics
+    ///
+    /// Panics if `finish` has been called.
+    pub fn push(&mut self, chunk: &[u8]) {
+        assert!(!self.finished, "push after finish");
+        self.compact();
+        self.buf.extend_from_slice(chunk);
+    }
+
+    /// Append a chunk of input, reusing its allocation if nothing else is buffered
+    ///
+    /// # Panics
+    ///
+    /// Panics if `finish` has been called.
+    pub fn push_vec(&mut self, chunk: Vec<u8>) {
+        assert!(!self.finished, "push after finish");
+        self.compact();
+        if self.buf.is_empty() {
+            self.buf = chunk;
+        } else {
+            self.buf.extend_from_slice(&chunk);
+        }
+    }
+
+    /// Mark the end of the input, so that an incomplete sequence at the end is reported as an
+    /// error rather than waiting for more bytes
+    pub fn finish(&mut self) {
+        self.finished = true;
+    }
+
+    /// Returns true if `finish` has been called
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    /// Returns the bytes that have been pushed but not decoded yet
+    pub fn pending(&self) -> &[u8] {
+        &self.buf[self.pos..]
+    }
+
+    /// Returns the number of bytes of the stream that have been decoded so far
+    pub fn byte_offset(&self) -> usize {
+        self.base + self.pos
+    }
+
+    /// Consume this struct and return the bytes that have not been decoded yet
+    pub fn into_pending(mut self) -> Vec<u8> {
+        self.buf.drain(..self.pos);
+        self.buf
+    }
+
+    /// Drop the decoded bytes from the front of the buffer
+    fn compact(&mut self) {
+        if self.pos > 0 {
+            self.buf.drain(..self.pos);
+            self.base += self.pos;
+            self.pos = 0;
+        }
+    }
+}
+
+impl Iterator for ChunkedChars {
+    type Item = Result<char, InvalidUtf8>;
+
+    fn next(&mut self) -> Option<Result<char, InvalidUtf8>> {
+        let start = self.pos;
+        let first = *self.buf.get(start)?;
+        if first.is_ascii() {
+            self.pos += 1;
+            return Some(Ok(first as char));
+        }
+
+        // a char is at most 4 bytes long
+        let bytes = &self.buf[start..self.buf.len().min(start + 4)];
+        let valid = match str::from_utf8(bytes) {
+            Ok(s) => s,
+            // the first char can be valid even if what follows it isn't
+            Err(e) if e.valid_up_to() > 0 => str::from_utf8(&bytes[..e.valid_up_to()]).unwrap(),
+            // error_len is None for a sequence that may still be completed by the next chunk
+            Err(e) => {
+                let len = match e.error_len() {
+                    Some(len) => len,
+                    None if self.finished => bytes.len(),
+                    None => return None,
+                };
+                self.pos += len;
+                return Some(Err(InvalidUtf8::new(self.base + start, len)));
+            }
+        };
+        let c = valid.chars().next().unwrap();
+        self.pos += c.len_utf8();
+        Some(Ok(c))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.buf.len() - self.pos))
+    }
+}
@@ -0,0 +1,75 @@
+//! Counting adapter
+
+use core::iter::{Iterator, DoubleEndedIterator, FusedIterator};
+use core::ops::Deref;
+
+/// Iterator adapter that counts the items yielded from the front
+///
+/// Created by the `counted` method on the owned iterators. The wrapped iterator's accessors (such
+/// as `as_str`) remain available through `Deref`.
+///
+/// # Example
+///
+/// ```rust
+/// # use owned_chars::OwnedCharsExt;
+/// let mut chars = String::from("héllo").into_char_indices().counted();
+/// chars.next();
+/// chars.next();
+/// assert_eq!(chars.chars_consumed(), 2);
+/// assert_eq!(chars.byte_offset(), 3);
+/// assert_eq!(chars.as_str(), "llo");
+/// ```
+#[derive(Debug)]
+pub struct Counted<I> {
+    iter: I,
+    count: usize,
+}
+
+impl<I> Counted<I> {
+    /// Wrap an iterator, starting the count at zero
+    pub fn new(iter: I) -> Self {
+        Counted { iter, count: 0 }
+    }
+
+    /// Returns the number of chars that have been yielded from the front (by `next` and the
+    /// methods built on it). Chars taken from the back are not counted.
+    pub fn chars_consumed(&self) -> usize {
+        self.count
+    }
+
+    /// Consume the adapter and return the wrapped iterator
+    pub fn into_inner(self) -> I {
+        self.iter
+    }
+}
+
+impl<I> Deref for Counted<I> {
+    type Target = I;
+
+    fn deref(&self) -> &I {
+        &self.iter
+    }
+}
+
+impl<I: Iterator> Iterator for Counted<I> {
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<I::Item> {
+        let item = self.iter.next();
+        if item.is_some() {
+            self.count += 1;
+        }
+        item
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<I: DoubleEndedIterator> DoubleEndedIterator for Counted<I> {
+    fn next_back(&mut self) -> Option<I::Item> {
+        self.iter.next_back()
+    }
+}
+
+impl<I: FusedIterator> FusedIterator for Counted<I> {}
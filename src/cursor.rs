@@ -0,0 +1,190 @@
+//! Lexer toolkit
+//!
+//! `StrCursor` bundles the primitives that hand-written lexers need (lookahead, conditional
+//! consumption, spans, line/column positions and backtracking) behind one type that owns its input.
+//!
+//! # Example
+//!
+//! ```rust
+//! use owned_chars::cursor::StrCursor;
+//!
+//! let mut cursor = StrCursor::new(String::from("let x = 42;"));
+//! let mut tokens = vec![];
+//! while !cursor.is_eof() {
+//!     cursor.skip_whitespace();
+//!     let start = cursor.mark();
+//!     match cursor.peek() {
+//!         Some(c) if c.is_alphabetic() => { cursor.eat_while(char::is_alphanumeric); }
+//!         Some(c) if c.is_ascii_digit() => { cursor.eat_while(|c| c.is_ascii_digit()); }
+//!         Some(_) => { cursor.bump(); }
+//!         None => break,
+//!     }
+//!     tokens.push((cursor.span_from(start), cursor.slice_from(start).to_owned()));
+//! }
+//! assert_eq!(tokens[3], (8..10, String::from("42")));
+//! ```
+
+use alloc::string::String;
+use core::ops::Range;
+use line_counted::LineCounter;
+use structs::{Checkpoint, OwnedCharIndices};
+
+/// Saved position of a `StrCursor`, see `StrCursor::mark`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Mark {
+    checkpoint: Checkpoint,
+    lines: LineCounter,
+}
+
+/// Cursor over an owned String, for writing lexers (see the module documentation)
+#[derive(Debug)]
+pub struct StrCursor {
+    chars: OwnedCharIndices,
+    /// line and column of the next char, updated by every method that consumes input
+    lines: LineCounter,
+}
+
+impl StrCursor {
+    /// Create Self from a String, moving the String into Self
+    pub fn new(s: String) -> Self {
+        StrCursor { chars: OwnedCharIndices::from_string(s), lines: LineCounter::new() }
+    }
+
+    /// Consume this struct and return the contained String
+    pub fn into_inner(self) -> String {
+        self.chars.into_inner()
+    }
+
+    /// Returns a string slice of contained `String`
+    pub fn get_inner(&self) -> &str {
+        self.chars.get_inner()
+    }
+
+    /// Returns the input that has not been consumed yet
+    pub fn as_str(&self) -> &str {
+        self.chars.as_str()
+    }
+
+    /// Returns the byte position of the next char
+    pub fn pos(&self) -> usize {
+        self.chars.byte_offset()
+    }
+
+    /// Returns the 1-based line and column (counted in chars) of the next char
+    ///
+    /// The position is kept up to date as input is consumed, so this takes constant time.
+    pub fn line_col(&self) -> (u32, u32) {
+        self.lines.get()
+    }
+
+    /// Returns true if all input has been consumed
+    pub fn is_eof(&self) -> bool {
+        self.chars.is_empty()
+    }
+
+    /// Returns the next char without consuming it
+    pub fn peek(&self) -> Option<char> {
+        self.peek_nth(0)
+    }
+
+    /// Returns the char `n` places after the next one without consuming anything
+    pub fn peek_nth(&self, n: usize) -> Option<char> {
+        self.chars.peek_nth(n).map(|(_, c)| c)
+    }
+
+    /// Consume and return the next char
+    pub fn bump(&mut self) -> Option<char> {
+        let (_, c) = self.chars.next()?;
+        self.lines.push_char(c);
+        Some(c)
+    }
+
+    /// Consume the next char if it is `expected`, returning whether it was
+    pub fn eat_char(&mut self, expected: char) -> bool {
+        let eaten = self.chars.next_if_eq(expected).is_some();
+        if eaten {
+            self.lines.push_char(expected);
+        }
+        eaten
+    }
+
+    /// Consume `prefix` if the remaining input starts with it, returning whether it did
+    pub fn eat(&mut self, prefix: &str) -> bool {
+        let eaten = self.chars.eat(prefix);
+        if eaten {
+            self.lines.push_str(prefix);
+        }
+        eaten
+    }
+
+    /// Consume chars while they match `f`, returning the consumed slice
+    pub fn eat_while<F: FnMut(char) -> bool>(&mut self, f: F) -> &str {
+        let start = self.pos();
+        self.chars.eat_while(f);
+        self.count_from(start)
+    }
+
+    /// Consume input up to the next occurrence of `c` (or the end), returning the consumed slice
+    pub fn skip_until(&mut self, c: char) -> &str {
+        let start = self.pos();
+        self.chars.skip_until(c);
+        self.count_from(start)
+    }
+
+    /// Consume any whitespace, returning the number of chars skipped
+    pub fn skip_whitespace(&mut self) -> usize {
+        let start = self.pos();
+        let skipped = self.chars.skip_whitespace();
+        self.count_from(start);
+        skipped
+    }
+
+    /// Update the line and column for the input consumed since `start`, and return that input
+    fn count_from(&mut self, start: usize) -> &str {
+        let consumed = &self.chars.get_inner()[start..self.chars.byte_offset()];
+        self.lines.push_str(consumed);
+        consumed
+    }
+
+    /// Returns a mark for the current position, for use with `span_from`, `slice_from` and
+    /// `rollback`
+    pub fn mark(&self) -> Mark {
+        Mark { checkpoint: self.chars.checkpoint(), lines: self.lines }
+    }
+
+    /// Return to a position saved with `mark`, including its line and column
+    pub fn rollback(&mut self, mark: Mark) {
+        self.chars.rollback(mark.checkpoint);
+        self.lines = mark.lines;
+    }
+
+    /// Returns the byte range from a mark to the current position (the span of the token that
+    /// started at the mark)
+    ///
+    /// # Panics
+    ///
+    /// Panics if the mark is after the current position.
+    pub fn span_from(&self, mark: Mark) -> Range<usize> {
+        let start = mark.checkpoint.start;
+        assert!(start <= self.pos(), "mark is after the current position");
+        start..self.pos()
+    }
+
+    /// Returns the text from a mark to the current position
+    ///
+    /// # Panics
+    ///
+    /// Panics if the mark is after the current position.
+    pub fn slice_from(&self, mark: Mark) -> &str {
+        self.slice(self.span_from(mark))
+    }
+
+    /// Returns the text of a span of the contained String
+    ///
+    /// # Panics
+    ///
+    /// Panics if the span is out of bounds or not on char boundaries.
+    pub fn slice(&self, span: Range<usize>) -> &str {
+        &self.get_inner()[span]
+    }
+}
@@ -0,0 +1,57 @@
+//! Deduplicating adapter
+
+use core::fmt;
+use core::ops::Deref;
+
+/// Iterator adapter that skips chars equal to the previous one (or having the same key)
+///
+/// Created by the `dedup_owned` and `dedup_by_key_owned` methods on the owned iterators. The
+/// wrapped iterator's accessors remain available through `Deref`. Duplicates are skipped when the
+/// next item is requested, so `as_str` may still start with some.
+///
+/// # Example
+///
+/// ```rust
+/// # use owned_chars::OwnedCharsExt;
+/// let chars = String::from("aaabccca").into_chars().dedup_owned();
+/// assert_eq!(chars.collect::<String>(), "abca");
+///
+/// let mut chars = String::from("a  \t b\n\nc").into_char_indices()
+///     .dedup_by_key_owned(char::is_whitespace);
+/// assert_eq!(chars.by_ref().take(3).collect::<Vec<_>>(), [(0, 'a'), (1, ' '), (5, 'b')]);
+/// assert_eq!(chars.as_str(), "\n\nc");
+/// ```
+#[derive(Clone)]
+pub struct Dedup<I, F, K> {
+    pub(crate) iter: I,
+    pub(crate) key: F,
+    /// key of the last char yielded
+    pub(crate) last: Option<K>,
+}
+
+impl<I, F, K> Dedup<I, F, K> {
+    /// Wrap an iterator, skipping the chars whose key (given by `key`) is the same as that of the
+    /// char before
+    pub fn new(iter: I, key: F) -> Self {
+        Dedup { iter, key, last: None }
+    }
+
+    /// Consume the adapter and return the wrapped iterator
+    pub fn into_inner(self) -> I {
+        self.iter
+    }
+}
+
+impl<I: fmt::Debug, F, K: fmt::Debug> fmt::Debug for Dedup<I, F, K> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Dedup").field("iter", &self.iter).field("last", &self.last).finish()
+    }
+}
+
+impl<I, F, K> Deref for Dedup<I, F, K> {
+    type Target = I;
+
+    fn deref(&self) -> &I {
+        &self.iter
+    }
+}
@@ -0,0 +1,133 @@
+//! Error types
+
+use alloc::string::String;
+use core::fmt;
+#[cfg(feature = "std")]
+use std::error::Error;
+
+/// Error returned when a byte position does not lie on a char boundary of the string (or is out of
+/// bounds)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NotCharBoundary {
+    offset: usize,
+}
+
+impl NotCharBoundary {
+    pub(crate) fn new(offset: usize) -> Self {
+        NotCharBoundary { offset }
+    }
+
+    /// Returns the offending byte position
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+}
+
+impl fmt::Display for NotCharBoundary {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "byte position {} is not a char boundary", self.offset)
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for NotCharBoundary {}
+
+/// Error returned when a shell-style word has a quote or backslash escape that is not terminated
+/// before the end of the input (see `OwnedShellWords`)
+#[cfg(feature = "shell")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UnterminatedQuote {
+    offset: usize,
+}
+
+#[cfg(feature = "shell")]
+impl UnterminatedQuote {
+    pub(crate) fn new(offset: usize) -> Self {
+        UnterminatedQuote { offset }
+    }
+
+    /// Returns the byte position of the opening quote or the backslash
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+}
+
+#[cfg(feature = "shell")]
+impl fmt::Display for UnterminatedQuote {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "unterminated quote or escape at byte position {}", self.offset)
+    }
+}
+
+#[cfg(all(feature = "std", feature = "shell"))]
+impl Error for UnterminatedQuote {}
+
+/// Error returned when a String that was expected to be ASCII is not (see `OwnedAsciiChars`)
+///
+/// The String can be recovered with `into_string`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NotAscii {
+    s: String,
+    offset: usize,
+}
+
+impl NotAscii {
+    pub(crate) fn new(s: String, offset: usize) -> Self {
+        NotAscii { s, offset }
+    }
+
+    /// Returns the byte position of the first non-ASCII char
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Consume this error and return the String
+    pub fn into_string(self) -> String {
+        self.s
+    }
+}
+
+impl fmt::Display for NotAscii {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "non-ASCII char at byte position {}", self.offset)
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for NotAscii {}
+
+/// Error for an invalid UTF-8 sequence (see `Utf8Chars`)
+#[cfg(feature = "decode")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InvalidUtf8 {
+    offset: usize,
+    len: usize,
+}
+
+#[cfg(feature = "decode")]
+impl InvalidUtf8 {
+    pub(crate) fn new(offset: usize, len: usize) -> Self {
+        InvalidUtf8 { offset, len }
+    }
+
+    /// Returns the byte position of the invalid sequence
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Returns the length of the invalid sequence in bytes (like `Utf8Error::error_len`, except
+    /// that a sequence cut short by the end of the input is also counted)
+    pub fn error_len(&self) -> usize {
+        self.len
+    }
+}
+
+#[cfg(feature = "decode")]
+impl fmt::Display for InvalidUtf8 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "invalid UTF-8 sequence of {} bytes at byte position {}", self.len, self.offset)
+    }
+}
+
+#[cfg(all(feature = "std", feature = "decode"))]
+impl Error for InvalidUtf8 {}
@@ -0,0 +1,72 @@
+//! Filtering adapter
+
+use core::fmt;
+use core::iter::{Iterator, DoubleEndedIterator, FusedIterator};
+use core::ops::Deref;
+
+/// Iterator adapter that only yields the items matching a predicate
+///
+/// Created by the `filter_owned` method on the owned iterators. Unlike `Iterator::filter`, the
+/// wrapped iterator's accessors remain available through `Deref`, and `into_inner` gives it back
+/// (and with it the String).
+///
+/// # Example
+///
+/// ```rust
+/// # use owned_chars::OwnedCharsExt;
+/// let mut chars = String::from("a\u{7}b\u{0}c").into_chars().filter_owned(|c| !c.is_control());
+/// assert_eq!(chars.by_ref().collect::<String>(), "abc");
+/// assert_eq!(chars.into_inner().into_inner(), "a\u{7}b\u{0}c");
+/// ```
+#[derive(Clone)]
+pub struct OwnedFilter<I, P> {
+    iter: I,
+    pred: P,
+}
+
+impl<I, P> OwnedFilter<I, P> {
+    /// Wrap an iterator, keeping only the items for which `pred` returns true
+    pub fn new(iter: I, pred: P) -> Self {
+        OwnedFilter { iter, pred }
+    }
+
+    /// Consume the adapter and return the wrapped iterator
+    pub fn into_inner(self) -> I {
+        self.iter
+    }
+}
+
+impl<I: fmt::Debug, P> fmt::Debug for OwnedFilter<I, P> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("OwnedFilter").field("iter", &self.iter).finish()
+    }
+}
+
+impl<I, P> Deref for OwnedFilter<I, P> {
+    type Target = I;
+
+    fn deref(&self) -> &I {
+        &self.iter
+    }
+}
+
+impl<I: Iterator, P: FnMut(&I::Item) -> bool> Iterator for OwnedFilter<I, P> {
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<I::Item> {
+        let pred = &mut self.pred;
+        self.iter.find(|item| pred(item))
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.iter.size_hint().1)
+    }
+}
+
+impl<I: DoubleEndedIterator, P: FnMut(&I::Item) -> bool> DoubleEndedIterator for OwnedFilter<I, P> {
+    fn next_back(&mut self) -> Option<I::Item> {
+        let pred = &mut self.pred;
+        self.iter.rfind(|item| pred(item))
+    }
+}
+
+impl<I: FusedIterator, P: FnMut(&I::Item) -> bool> FusedIterator for OwnedFilter<I, P> {}
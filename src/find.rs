@@ -0,0 +1,143 @@
+//! Owned substring search
+
+use alloc::borrow::ToOwned;
+use alloc::string::String;
+use core::iter::{Iterator, FusedIterator};
+
+/// Forward search for a substring, shared by the iterators that need one
+#[derive(Debug, Clone)]
+pub(crate) struct Searcher {
+    pat: String,
+    /// where to search for the next match
+    position: usize,
+    overlapping: bool,
+    /// quote and escape chars, if matches inside quotes are to be skipped
+    quoting: Option<(char, Option<char>)>,
+}
+
+impl Searcher {
+    pub(crate) fn new(pat: &str) -> Self {
+        Searcher { pat: pat.to_owned(), position: 0, overlapping: false, quoting: None }
+    }
+
+    /// Skip matches between pairs of `quote` chars, and matches or quotes right after `escape`
+    pub(crate) fn set_quoting(&mut self, quote: char, escape: Option<char>) {
+        self.quoting = Some((quote, escape));
+    }
+
+    /// Returns the byte position where the next search will start
+    pub(crate) fn position(&self) -> usize {
+        self.position
+    }
+
+    /// Returns the byte range of the next match of the pattern in `haystack`, moving the search
+    /// position past it (or just past its first char, when searching for overlapping matches)
+    pub(crate) fn next_match(&mut self, haystack: &str) -> Option<(usize, usize)> {
+        if let Some((quote, escape)) = self.quoting {
+            let rest = haystack.get(self.position..)?;
+            let a = self.position + self.find_unquoted(rest, quote, escape)?;
+            let b = a + self.pat.len();
+            self.position = match haystack[a..].chars().next() {
+                Some(c) if self.overlapping || self.pat.is_empty() => a + c.len_utf8(),
+                Some(_) => b,
+                None => haystack.len() + 1,
+            };
+            Some((a, b))
+        } else if self.pat.is_empty() {
+            // the empty pattern matches at every char boundary, including the end
+            let m = self.position;
+            self.position = match haystack.get(m..)?.chars().next() {
+                Some(c) => m + c.len_utf8(),
+                None => haystack.len() + 1,
+            };
+            Some((m, m))
+        } else {
+            let a = self.position + haystack[self.position..].find(&*self.pat)?;
+            let b = a + self.pat.len();
+            self.position = if self.overlapping {
+                a + haystack[a..].chars().next().map_or(1, char::len_utf8)
+            } else {
+                b
+            };
+            Some((a, b))
+        }
+    }
+
+    /// Returns the position of the first match of the pattern in `s` that is not quoted or escaped
+    fn find_unquoted(&self, s: &str, quote: char, escape: Option<char>) -> Option<usize> {
+        let mut in_quotes = false;
+        let mut i = 0;
+        loop {
+            if !in_quotes && s[i..].starts_with(&*self.pat) {
+                return Some(i);
+            }
+            let c = s[i..].chars().next()?;
+            i += c.len_utf8();
+            if Some(c) == escape {
+                i += s[i..].chars().next().map_or(0, char::len_utf8);
+            } else if c == quote {
+                in_quotes = !in_quotes;
+            }
+        }
+    }
+}
+
+/// Iterator over the byte positions of the occurrences of a substring in a string (the string and
+/// the substring are owned by the iterator)
+///
+/// By default only non-overlapping occurrences are found, giving the same positions as
+/// `str::match_indices`; see `overlapping`. The search uses std's (Two-Way) substring search.
+///
+/// # Example
+///
+/// ```rust
+/// # use owned_chars::OwnedFind;
+/// let found = OwnedFind::from_string(String::from("aaaa"), "aa");
+/// assert_eq!(found.collect::<Vec<_>>(), [0, 2]);
+///
+/// let found = OwnedFind::from_string(String::from("aaaa"), "aa").overlapping();
+/// assert_eq!(found.collect::<Vec<_>>(), [0, 1, 2]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct OwnedFind {
+    s: String,
+    searcher: Searcher,
+}
+
+impl OwnedFind {
+    /// Create Self from a String, moving the String into Self
+    pub fn from_string(s: String, pat: &str) -> Self {
+        OwnedFind { s, searcher: Searcher::new(pat) }
+    }
+
+    /// Also find occurrences that overlap with the previous one
+    pub fn overlapping(mut self) -> Self {
+        self.searcher.overlapping = true;
+        self
+    }
+
+    /// Consume this struct and return the contained String
+    pub fn into_inner(self) -> String {
+        self.s
+    }
+
+    /// Returns a string slice of contained `String`
+    pub fn get_inner(&self) -> &str {
+        &self.s
+    }
+
+    /// Returns the part of the contained String that has not been searched yet
+    pub fn as_str(&self) -> &str {
+        self.s.get(self.searcher.position()..).unwrap_or("")
+    }
+}
+
+impl Iterator for OwnedFind {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        self.searcher.next_match(&self.s).map(|(start, _)| start)
+    }
+}
+
+iter_traits!(OwnedFind, usize, [FusedIterator]);
@@ -0,0 +1,86 @@
+//! Intersperse adapter
+
+use alloc::string::String;
+use core::iter::{Iterator, FusedIterator};
+use core::ops::Deref;
+use OwnedChars;
+
+/// Iterator adapter that yields the chars of a separator between each pair of chars
+///
+/// Created by `OwnedChars::intersperse_owned`. The wrapped iterator's accessors remain available
+/// through `Deref`. No lookahead is needed, so `as_str` is exactly the input that has not been
+/// yielded yet.
+///
+/// # Example
+///
+/// ```rust
+/// # use owned_chars::OwnedCharsExt;
+/// let spaced = String::from("abc").into_chars().intersperse_owned(' ');
+/// assert_eq!(spaced.collect::<String>(), "a b c");
+///
+/// let mut arrows = String::from("xyz").into_chars().intersperse_owned(" -> ");
+/// assert_eq!(arrows.by_ref().take(5).collect::<String>(), "x -> ");
+/// assert_eq!(arrows.as_str(), "yz");
+/// ```
+#[derive(Debug, Clone)]
+pub struct Intersperse<I> {
+    iter: I,
+    sep: String,
+    /// byte position in `sep` of the next separator char to yield (`sep.len()` if there is none)
+    sep_pos: usize,
+    /// whether a separator is due before the next char
+    needs_sep: bool,
+}
+
+impl<I> Intersperse<I> {
+    /// Consume the adapter and return the wrapped iterator
+    pub fn into_inner(self) -> I {
+        self.iter
+    }
+}
+
+impl<I> Deref for Intersperse<I> {
+    type Target = I;
+
+    fn deref(&self) -> &I {
+        &self.iter
+    }
+}
+
+impl OwnedChars {
+    /// Wrap this iterator in an adapter that yields `sep` (a char or a string) between each pair of
+    /// chars, keeping the accessors available (see `Intersperse`)
+    pub fn intersperse_owned<S: Into<String>>(self, sep: S) -> Intersperse<Self> {
+        let sep = sep.into();
+        Intersperse { iter: self, sep_pos: sep.len(), sep, needs_sep: false }
+    }
+}
+
+impl Iterator for Intersperse<OwnedChars> {
+    type Item = char;
+
+    fn next(&mut self) -> Option<char> {
+        if self.needs_sep && !self.iter.is_empty() {
+            self.needs_sep = false;
+            self.sep_pos = 0;
+        }
+        if let Some(c) = self.sep[self.sep_pos..].chars().next() {
+            self.sep_pos += c.len_utf8();
+            return Some(c);
+        }
+        let c = self.iter.next()?;
+        self.needs_sep = true;
+        Some(c)
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let pending = self.sep[self.sep_pos..].chars().count();
+        let sep_len = self.sep.chars().count();
+        // a separator goes before each remaining char, except the next one if none is due
+        let seps = |n: usize| if self.needs_sep { n } else { n.saturating_sub(1) };
+        let (lower, upper) = self.iter.size_hint();
+        let total = |n: usize| seps(n).checked_mul(sep_len)?.checked_add(n)?.checked_add(pending);
+        (total(lower).unwrap_or(usize::MAX), upper.and_then(total))
+    }
+}
+
+iter_traits!(Intersperse<OwnedChars>, char, [FusedIterator]);
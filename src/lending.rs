@@ -0,0 +1,36 @@
+//! Lending iterators
+//!
+//! A lending iterator can yield items that borrow from the iterator itself. That lets the owned
+//! iterators in this crate hand out `&str` slices of their String, without allocating a new String
+//! per item, while still being usable in generic code.
+//!
+//! The owned iterators also implement `Iterator`, so if both traits are in scope, call this one as
+//! `LendingIterator::next(&mut iter)` (or use the inherent `next_str` methods).
+
+/// An iterator whose items may borrow from the iterator
+///
+/// # Example
+///
+/// ```rust
+/// use owned_chars::OwnedLines;
+/// use owned_chars::lending::LendingIterator;
+///
+/// fn longest<I>(mut iter: I) -> usize
+///     where I: for<'a> LendingIterator<Item<'a> = &'a str>
+/// {
+///     let mut longest = 0;
+///     while let Some(s) = LendingIterator::next(&mut iter) {
+///         longest = longest.max(s.len());
+///     }
+///     longest
+/// }
+///
+/// assert_eq!(longest(OwnedLines::from_string(String::from("a\nbcd\nef"))), 3);
+/// ```
+pub trait LendingIterator {
+    /// The type of the items, which may borrow from the iterator
+    type Item<'a> where Self: 'a;
+
+    /// Advance the iterator and return the next item
+    fn next(&mut self) -> Option<Self::Item<'_>>;
+}
@@ -1,77 +1,827 @@
 #![deny(missing_docs)]
+#![no_std]
+#![cfg_attr(feature = "nightly-std", feature(string_into_chars))]
 
 //! This crate provides two owned iterators over String: OwnedChars and OwnedCharIndices. They have
 //! the same output as Chars and CharIndices, but creating the iterator consumes the String as
 //! opposed to borrowing.
+//!
+//! The crate works without std (but needs `alloc`) if the default `std` feature is disabled. With
+//! the `safe` feature, the iterators over chars and bytes (`OwnedChars` and the other iterators
+//! sharing its implementation, and `OwnedBytes`) contain no unsafe code: slicing the String is
+//! bounds-checked, and the byte iterator keeps byte offsets instead of a borrowing iterator. The
+//! feature does not cover `Owned`, `owned_iter!` and `#[owned_iterator]`, which have to hold a
+//! String together with an iterator borrowing from it and can't do that without unsafe code.
+//! `set_position_unchecked` stays an `unsafe fn` so that enabling the feature doesn't change the
+//! API, but it checks the position.
+//!
+//! Iterator families beyond the core ones are behind features that are off by default: `shell`
+//! (`OwnedShellWords`), `decode` (`Utf8Chars`, `ChunkedChars`), `cursor`, `tokens`, `lending` and
+//! `derive`. The `full` feature enables all of them.
 //! 
 //! Do you think this should be included in Rust proper? [Comment
 //! here](https://github.com/durka/owned-chars/issues/5) if so!
 
-/// Extension trait for String providing owned char and char-index iterators
+extern crate alloc;
+#[cfg(any(feature = "std", test))]
+#[macro_use]
+extern crate std;
+
+use alloc::borrow::{Cow, ToOwned};
+use alloc::boxed::Box;
+use alloc::rc::Rc;
+use alloc::string::String;
+#[cfg(target_has_atomic = "ptr")]
+use alloc::sync::Arc;
+#[cfg(test)]
+use alloc::{string::ToString, vec::Vec};
+
+/// Extension trait for String providing the owned iterators of this crate
+///
+/// Only `into_chars` and `into_char_indices` have to be implemented: the other methods take the
+/// String back out of `into_chars`.
+///
+/// The impls for `String` and `Box<str>` reuse the existing allocation. The ones for `&str`,
+/// `Rc<str>` and `Arc<str>` (and `Cow<str>` when it is borrowed) copy the text into a new String,
+/// which takes time and memory proportional to its length.
 pub trait OwnedCharsExt {
     /// Gets an owning iterator over the chars (see `chars()`)
     fn into_chars(self) -> OwnedChars;
+    /// Gets an owning iterator over the chars from back to front (see `OwnedCharsRev`)
+    fn into_chars_rev(self) -> OwnedCharsRev where Self: Sized {
+        OwnedCharsRev::from_string(self.into_chars().into_inner())
+    }
     /// Gets an owning iterator over the chars and their indices (see `char_indices()`)
     fn into_char_indices(self) -> OwnedCharIndices;
+    /// Gets an owning iterator over the chars and their byte ranges (see `OwnedCharRanges`)
+    fn into_char_ranges(self) -> OwnedCharRanges where Self: Sized {
+        OwnedCharRanges::from_string(self.into_chars().into_inner())
+    }
+    /// Gets an owning iterator over the Unicode scalar values as `u32` (see `OwnedCodePoints`)
+    fn into_code_points(self) -> OwnedCodePoints where Self: Sized {
+        OwnedCodePoints::from_string(self.into_chars().into_inner())
+    }
+    /// Gets an owning iterator over the Unicode scalar values as `u32` and their indices (see
+    /// `OwnedCodePointIndices`)
+    fn into_code_point_indices(self) -> OwnedCodePointIndices where Self: Sized {
+        OwnedCodePointIndices::from_string(self.into_chars().into_inner())
+    }
+    /// Gets an owning iterator over the chars if the string is ASCII, or gives the string back in
+    /// the error (see `OwnedAsciiChars`)
+    fn try_into_ascii_chars(self) -> Result<OwnedAsciiChars, NotAscii> where Self: Sized {
+        OwnedAsciiChars::from_string(self.into_chars().into_inner())
+    }
+    /// Gets an owning iterator over the bytes (see `bytes()`; this isn't called `into_bytes`
+    /// because `String::into_bytes` would take precedence)
+    fn into_owned_bytes(self) -> OwnedBytes where Self: Sized {
+        OwnedBytes::from_string(self.into_chars().into_inner())
+    }
+    /// Gets an owning iterator over the UTF-8 encoding of each char (see `OwnedCharBytes`)
+    fn into_char_bytes(self) -> OwnedCharBytes where Self: Sized {
+        OwnedCharBytes::from_string(self.into_chars().into_inner())
+    }
+    /// Gets an owning iterator over the lines (see `lines()` and `OwnedLines`)
+    fn into_lines(self) -> OwnedLines where Self: Sized {
+        OwnedLines::from_string(self.into_chars().into_inner())
+    }
+    /// Gets an owning iterator over the substrings separated by `pat` (see `split()` and
+    /// `OwnedSplit`)
+    fn into_split(self, pat: &str) -> OwnedSplit where Self: Sized {
+        OwnedSplit::from_string(self.into_chars().into_inner(), pat)
+    }
+    /// Gets an owning iterator over at most `n` substrings separated by `pat` (see `splitn()` and
+    /// `OwnedSplitN`)
+    fn into_splitn(self, n: usize, pat: &str) -> OwnedSplitN where Self: Sized {
+        OwnedSplitN::from_string(self.into_chars().into_inner(), n, pat)
+    }
+    /// Gets an owning iterator over the byte positions of `pat` (see `OwnedFind`)
+    fn into_find(self, pat: &str) -> OwnedFind where Self: Sized {
+        OwnedFind::from_string(self.into_chars().into_inner(), pat)
+    }
+    /// Gets an owning iterator over the shell-style words (see `OwnedShellWords`; requires the
+    /// `shell` feature)
+    #[cfg(feature = "shell")]
+    fn into_shell_words(self) -> OwnedShellWords where Self: Sized {
+        OwnedShellWords::from_string(self.into_chars().into_inner())
+    }
 }
 
-impl OwnedCharsExt for String {
-    fn into_chars(self) -> OwnedChars {
-        OwnedChars::from_string(self)
-    }
+/// Implements `OwnedCharsExt` for a string type by converting it into a String
+macro_rules! ext_impls {
+    ($(#[$attr:meta])* impl for $t:ty, |$s:ident| $to_string:expr) => {
+        $(#[$attr])*
+        impl OwnedCharsExt for $t {
+            fn into_chars(self) -> OwnedChars {
+                let $s = self;
+                OwnedChars::from_string($to_string)
+            }
 
-    fn into_char_indices(self) -> OwnedCharIndices {
-        OwnedCharIndices::from_string(self)
-    }
+            fn into_char_indices(self) -> OwnedCharIndices {
+                let $s = self;
+                OwnedCharIndices::from_string($to_string)
+            }
+        }
+    };
+}
+
+ext_impls! {
+    /// Moves the String into the iterator without copying
+    impl for String, |s| s
+}
+
+ext_impls! {
+    /// Copies the str into a new String, so that borrowed and owned strings can be handled
+    /// uniformly
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use owned_chars::{OwnedChars, OwnedCharsExt};
+    /// fn chars_of<S: OwnedCharsExt>(s: S) -> OwnedChars {
+    ///     s.into_chars()
+    /// }
+    ///
+    /// assert_eq!(chars_of("abc").as_str(), "abc");
+    /// assert_eq!(chars_of(String::from("abc")).as_str(), "abc");
+    /// ```
+    impl for &str, |s| s.to_owned()
+}
+
+ext_impls! {
+    /// Reuses the allocation of the box
+    impl for Box<str>, |s| s.into_string()
 }
 
+ext_impls! {
+    /// Reuses the allocation if the Cow is owned, and copies the str otherwise
+    impl for Cow<'_, str>, |s| s.into_owned()
+}
+
+ext_impls! {
+    /// Copies the str into a new String, unlike the `String` and `Box<str>` impls: the text may be
+    /// shared, and even the last `Rc` keeps it in an allocation that a String can't take over
+    impl for Rc<str>, |s| String::from(&*s)
+}
+
+ext_impls! {
+    /// Copies the str into a new String, unlike the `String` and `Box<str>` impls: the text may be
+    /// shared, and even the last `Arc` keeps it in an allocation that a String can't take over
+    #[cfg(target_has_atomic = "ptr")]
+    impl for Arc<str>, |s| String::from(&*s)
+}
+
+#[macro_use]
+mod macros;
+#[doc(hidden)]
+pub use macros::__private;
+
+mod counted;
+pub use counted::Counted;
+
+mod take;
+pub use take::{SkipChars, TakeChars};
+
+mod peekable;
+pub use peekable::OwnedPeekable;
+
+mod filter;
+pub use filter::OwnedFilter;
+
+mod position;
+pub use position::{Position, WithPosition};
+
+mod intersperse;
+pub use intersperse::Intersperse;
+
+mod dedup;
+pub use dedup::Dedup;
+
+mod reclaim;
+pub use reclaim::Reclaiming;
+
+mod line_counted;
+pub use line_counted::LineCounted;
+
+mod error;
+pub use error::{NotAscii, NotCharBoundary};
+#[cfg(feature = "decode")]
+pub use error::InvalidUtf8;
+#[cfg(feature = "shell")]
+pub use error::UnterminatedQuote;
+
+mod ascii;
+pub use ascii::{KnownAscii, OwnedAsciiChars};
+
+mod find;
+pub use find::OwnedFind;
+
+mod split;
+pub use split::{OwnedSplit, OwnedSplitN};
+
+mod lines;
+pub use lines::OwnedLines;
+
+mod zip;
+pub use zip::OwnedZipChars;
+
+mod char_bytes;
+pub use char_bytes::OwnedCharBytes;
+
+mod rev;
+pub use rev::OwnedCharsRev;
+
+#[cfg(feature = "shell")]
+mod shell;
+#[cfg(feature = "shell")]
+pub use shell::OwnedShellWords;
+
+#[cfg(feature = "decode")]
+mod utf8;
+#[cfg(feature = "decode")]
+pub use utf8::Utf8Chars;
+
+#[cfg(feature = "decode")]
+mod chunked;
+#[cfg(feature = "decode")]
+pub use chunked::ChunkedChars;
+
+#[cfg(feature = "std")]
+mod read;
+#[cfg(feature = "std")]
+pub use read::{OwnedReadLines, ReadChars};
+
+mod owned_str;
+pub use owned_str::{OwnedStr, OwnedStrIndices};
+
+mod owned;
+pub use owned::{Owned, StableStr, StrIterFn};
+
+#[cfg(feature = "cursor")]
+pub mod cursor;
+
+pub mod prelude;
+
+#[cfg(feature = "tokens")]
+pub mod tokens;
+
+#[cfg(feature = "lending")]
+pub mod lending;
+
+#[cfg(feature = "derive")]
+extern crate owned_chars_derive;
+
+/// Attribute that turns a unit struct into an owned iterator (requires the `derive` feature)
+///
+/// This is a shorthand for `owned_iter!`. For the iterator methods of `str` it is enough to name
+/// the method (`chars`, `char_indices`, `bytes`, `lines`, `split_whitespace`,
+/// `split_ascii_whitespace`, `encode_utf16` and the `escape_*` methods), and items that borrow
+/// from the String are converted to String. Any other iterator is described with `inner` (its
+/// type, borrowing for `'a`), `item`, `new` (a function from `&str` to the iterator), and
+/// optionally `map` (a function converting the items) and `traits`.
+///
+/// # Example
+///
+/// ```rust
+/// #[macro_use] extern crate owned_chars;
+/// use owned_chars::owned_iterator;
+///
+/// /// Iterator over the lines of a String
+/// #[owned_iterator(str::lines)]
+/// pub struct Lines;
+///
+/// #[owned_iterator(bytes)]
+/// struct Bytes;
+///
+/// fn digits(s: &str) -> std::str::Matches<fn(char) -> bool> {
+///     s.matches(char::is_numeric)
+/// }
+///
+/// fn parse(d: &str) -> u32 {
+///     d.parse().unwrap()
+/// }
+///
+/// #[owned_iterator(inner = std::str::Matches<'a, fn(char) -> bool>, item = u32,
+///                  new = digits, map = parse, traits = [DoubleEndedIterator])]
+/// pub struct Digits;
+///
+/// # fn main() {
+/// let mut lines = Lines::from_string(String::from("one\ntwo\n"));
+/// assert_eq!(lines.next_back(), Some(String::from("two")));
+///
+/// let bytes = Bytes::from_string(String::from("héllo"));
+/// assert_eq!(bytes.len(), 6);
+///
+/// let digits = Digits::from_string(String::from("a1b2c3"));
+/// assert_eq!(digits.rev().collect::<Vec<_>>(), [3, 2, 1]);
+/// # }
+/// ```
+///
+/// As with `owned_iter!`, the String and the iterator borrowing from it can't be reached
+/// directly:
+///
+/// ```rust,compile_fail
+/// use owned_chars::owned_iterator;
+///
+/// #[owned_iterator(str::split_whitespace)]
+/// struct Words;
+///
+/// let mut words = Words::from_string(String::from("owned words"));
+/// let leaked: &'static str = words.i.next().unwrap();
+/// ```
+///
+/// ```rust,compile_fail
+/// use owned_chars::owned_iterator;
+///
+/// #[owned_iterator(str::split_whitespace)]
+/// struct Words;
+///
+/// let mut words = Words::from_string(String::from("owned words"));
+/// words.s.push_str(" and more");
+/// ```
+#[cfg(feature = "derive")]
+pub use owned_chars_derive::owned_iterator;
+
 /// structs
+#[cfg_attr(feature = "safe", deny(unsafe_code))]
 mod structs {
-    use std::str::{Chars, CharIndices};
-    use std::iter::{Iterator, DoubleEndedIterator, FusedIterator};
-    use std::mem::transmute;
+    use alloc::borrow::ToOwned;
+    use alloc::string::{FromUtf16Error, String};
+    use alloc::vec::Vec;
+    use core::fmt;
+    use core::iter::{Iterator, DoubleEndedIterator, ExactSizeIterator, FusedIterator};
+    use core::ops::Range;
+    #[cfg(not(feature = "safe"))]
+    use core::{mem::transmute, slice};
+    use ascii::KnownAscii;
+    use counted::Counted;
+    use error::NotCharBoundary;
+    use dedup::Dedup;
+    use filter::OwnedFilter;
+    use line_counted::LineCounted;
+    use peekable::OwnedPeekable;
+    use position::{Position, WithPosition};
+    use reclaim::Reclaiming;
+    use take::{skip_str, take_str, SkipChars, TakeChars};
 
-    /// Iterator over the chars of a string (the string is owned by the iterator)
-    #[derive(Debug)]
-    pub struct OwnedChars {
-        s: String,
-        i: Chars<'static>,
+    /// Declares an owned iterator over the chars of a String (its methods are added by `impls!`)
+    macro_rules! owned_struct {
+        ($(#[$attr:meta])* $owned_struct:ident) => {
+            $(#[$attr])*
+            #[derive(Clone)]
+            pub struct $owned_struct {
+                /// the input, truncated as chars are consumed from the back
+                s: String,
+                /// byte position of the part that has not been consumed
+                start: usize,
+            }
+        };
     }
 
-    /// Iterator over the chars of a string and their indices (the string is owned by the iterator)
-    #[derive(Debug)]
-    pub struct OwnedCharIndices {
-        s: String,
-        i: CharIndices<'static>,
+    owned_struct! {
+        /// Iterator over the chars of a string (the string is owned by the iterator)
+        OwnedChars
+    }
+
+    owned_struct! {
+        /// Iterator over the chars of a string and their indices (the string is owned by the
+        /// iterator)
+        OwnedCharIndices
+    }
+
+    owned_struct! {
+        /// Iterator over the chars of a string and their byte ranges (the string is owned by the
+        /// iterator)
+        ///
+        /// This is like `OwnedCharIndices`, but each item also gives the end of the char, which
+        /// saves calling `len_utf8` when building spans.
+        ///
+        /// # Example
+        ///
+        /// ```rust
+        /// # use owned_chars::OwnedCharsExt;
+        /// let mut ranges = String::from("hé!").into_char_ranges();
+        /// assert_eq!(ranges.next(), Some((0..1, 'h')));
+        /// assert_eq!(ranges.next(), Some((1..3, 'é')));
+        /// assert_eq!(ranges.next_back(), Some((3..4, '!')));
+        /// ```
+        OwnedCharRanges
+    }
+
+    owned_struct! {
+        /// Iterator over the Unicode scalar values of a string, as `u32` (the string is owned by
+        /// the iterator)
+        ///
+        /// # Example
+        ///
+        /// ```rust
+        /// # use owned_chars::OwnedCharsExt;
+        /// let codes = String::from("hé€").into_code_points();
+        /// assert_eq!(codes.collect::<Vec<_>>(), [0x68, 0xe9, 0x20ac]);
+        /// ```
+        OwnedCodePoints
+    }
+
+    owned_struct! {
+        /// Iterator over the Unicode scalar values of a string, as `u32`, and their byte positions
+        /// (the string is owned by the iterator)
+        OwnedCodePointIndices
+    }
+
+    /// Shrink a String to the given byte range (which must be on char boundaries) by moving that
+    /// range to the front of the existing allocation
+    #[cfg(not(feature = "safe"))]
+    fn keep_range(s: &mut String, start: usize, end: usize) {
+        assert!(s.get(start..end).is_some(), "range is not on char boundaries");
+        // the range is a whole number of chars, so the bytes stay valid UTF-8
+        let v = unsafe { s.as_mut_vec() };
+        v.copy_within(start..end, 0);
+        v.truncate(end - start);
+    }
+
+    /// Shrink a String to the given byte range (which must be on char boundaries) by moving that
+    /// range to the front of the existing allocation
+    #[cfg(feature = "safe")]
+    fn keep_range(s: &mut String, start: usize, end: usize) {
+        s.truncate(end);
+        s.drain(..start);
+    }
+
+    /// Number of chars of the remaining input shown by the `Debug` impls
+    const DEBUG_CHARS: usize = 32;
+
+    /// Debug view of the start of a string, cut off after `DEBUG_CHARS` chars
+    struct Truncated<'a>(&'a str);
+
+    impl<'a> fmt::Debug for Truncated<'a> {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            match self.0.char_indices().nth(DEBUG_CHARS) {
+                Some((i, _)) => write!(f, "{:?}...", &self.0[..i]),
+                None => write!(f, "{:?}", self.0),
+            }
+        }
+    }
+
+    /// Saved position of an owned iterator, see `checkpoint` and `rollback`
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct Checkpoint {
+        pub(crate) start: usize,
+    }
+
+    /// Implements the iterator traits for `LineCounted` over an owned iterator (or an adapter that
+    /// derefs to one)
+    macro_rules! line_counted_impls {
+        ($iter:ty, $item:ty) => {
+            impl Iterator for LineCounted<$iter> {
+                type Item = $item;
+
+                fn next(&mut self) -> Option<$item> {
+                    let c = self.iter.as_str().chars().next()?;
+                    let item = self.iter.next();
+                    self.lines.push_char(c);
+                    item
+                }
+                fn size_hint(&self) -> (usize, Option<usize>) {
+                    self.iter.size_hint()
+                }
+            }
+
+            impl DoubleEndedIterator for LineCounted<$iter> {
+                fn next_back(&mut self) -> Option<$item> {
+                    self.iter.next_back()
+                }
+            }
+
+            iter_traits!(LineCounted<$iter>, $item, [FusedIterator]);
+        };
     }
 
     macro_rules! impls {
-        ($owned_struct:ident, $target_struct:ident, $method: ident, $item: ty) => {
+        ($owned_struct:ident, $item:ty, |$offset:ident, $c:ident| $make_item:expr) => {
             impl $owned_struct {
                 /// Create Self from a String, moving the String into Self
                 pub fn from_string(s: String) -> Self {
-                    unsafe {
-                        // First, we can call .chars/.char_indices, whose result will have the same
-                        // lifetime as the owner. We need the transmute to "widen" the lifetime into
-                        // 'static which allows us to store it in the struct.
-                        //
-                        // The struct fields are private, so users can't observe this fake static
-                        // lifetime. Code within this module must never destructure the struct
-                        // because it risks losing track of the real lifetime!
-                        let i = transmute::<$target_struct, $target_struct<'static>>(s.$method());
+                    let end = s.len();
+                    Self::from_range(s, 0, end)
+                }
+
+                /// Create Self from UTF-16 (such as a Windows or JavaScript string), decoding it into
+                /// a new String
+                ///
+                /// Returns an error if the input contains unpaired surrogates.
+                ///
+                /// # Example
+                ///
+                /// ```rust
+                /// # use owned_chars::OwnedChars;
+                /// let utf16 = vec![0x68, 0xe9, 0xd834, 0xdd1e];
+                /// let chars = OwnedChars::try_from_utf16(&utf16).unwrap();
+                /// assert_eq!(chars.as_str(), "hé𝄞");
+                ///
+                /// assert!(OwnedChars::try_from_utf16(&[0xd834]).is_err());
+                /// assert_eq!(OwnedChars::from_utf16_lossy(&[0x61, 0xd834]).as_str(), "a\u{fffd}");
+                /// ```
+                pub fn try_from_utf16(v: &[u16]) -> Result<Self, FromUtf16Error> {
+                    String::from_utf16(v).map(Self::from_string)
+                }
+
+                /// Create Self from UTF-16, decoding it into a new String and replacing unpaired
+                /// surrogates with U+FFFD REPLACEMENT CHARACTER
+                pub fn from_utf16_lossy(v: &[u16]) -> Self {
+                    Self::from_string(String::from_utf16_lossy(v))
+                }
+
+                /// Create Self from bytes that should be UTF-8, replacing invalid sequences with
+                /// U+FFFD REPLACEMENT CHARACTER like `String::from_utf8_lossy`
+                ///
+                /// If the bytes are valid, the vector's allocation is reused instead of copied.
+                ///
+                /// # Example
+                ///
+                /// ```rust
+                /// # use owned_chars::OwnedChars;
+                /// let chars = OwnedChars::from_utf8_lossy_owned(b"caf\xc3\xa9".to_vec());
+                /// assert_eq!(chars.as_str(), "café");
+                ///
+                /// let chars = OwnedChars::from_utf8_lossy_owned(b"caf\xc3".to_vec());
+                /// assert_eq!(chars.as_str(), "caf\u{fffd}");
+                /// ```
+                pub fn from_utf8_lossy_owned(v: Vec<u8>) -> Self {
+                    let s = match String::from_utf8(v) {
+                        Ok(s) => s,
+                        Err(e) => String::from_utf8_lossy(e.as_bytes()).into_owned(),
+                    };
+                    Self::from_string(s)
+                }
 
-                        // Now, move the string (but not the string data!)
-                        $owned_struct { s, i }
+                /// Create Self from a String and a byte position (such as one returned by
+                /// `into_parts`), resuming iteration at that position
+                ///
+                /// Returns an error if the position is not on a char boundary.
+                ///
+                /// # Example
+                ///
+                /// ```rust
+                /// # use owned_chars::{OwnedCharIndices, OwnedCharsExt};
+                /// let mut chars = String::from("héllo").into_char_indices();
+                /// chars.next();
+                /// let (s, offset) = chars.into_parts();
+                /// let mut chars = OwnedCharIndices::from_parts(s, offset).unwrap();
+                /// assert_eq!(chars.next(), Some((1, 'é')));
+                ///
+                /// assert!(OwnedCharIndices::from_parts(String::from("héllo"), 2).is_err());
+                /// ```
+                pub fn from_parts(s: String, offset: usize) -> Result<Self, NotCharBoundary> {
+                    if s.is_char_boundary(offset) {
+                        let end = s.len();
+                        Ok(Self::from_range(s, offset, end))
+                    } else {
+                        Err(NotCharBoundary::new(offset))
                     }
                 }
 
+                /// Create Self iterating over the given byte range of a String, dropping everything
+                /// after the range (panics if the range is not on char boundaries)
+                fn from_range(mut s: String, start: usize, end: usize) -> Self {
+                    assert!(s.get(start..end).is_some(), "range is not on char boundaries");
+                    s.truncate(end);
+                    $owned_struct { s, start }
+                }
+
+                /// Restrict iteration to the given byte range of the contained String, dropping
+                /// everything after it (panics if the range is not on char boundaries)
+                ///
+                /// The end cannot move forward again, since the chars after it are gone.
+                fn set_range(&mut self, start: usize, end: usize) {
+                    assert!(self.s.get(start..end).is_some(), "range is not on char boundaries");
+                    self.s.truncate(end);
+                    self.start = start;
+                }
+
+                /// Returns the byte position just past the last char that has not been consumed
+                /// from the back
+                fn end_offset(&self) -> usize {
+                    self.s.len()
+                }
+
+                /// Returns the part of the contained String that has not been consumed
+                #[cfg(not(feature = "safe"))]
+                fn rest(&self) -> &str {
+                    // The position is checked when it is set (except by `set_position_unchecked`,
+                    // whose caller promises it is valid), `pop_front` only moves it by whole
+                    // chars, and the String is only truncated at or after it, so it is always a
+                    // char boundary within the String.
+                    unsafe { self.s.get_unchecked(self.start..) }
+                }
+
+                /// Returns the part of the contained String that has not been consumed
+                #[cfg(feature = "safe")]
+                fn rest(&self) -> &str {
+                    &self.s[self.start..]
+                }
+
+                /// Consume the next char from the front
+                fn pop_front(&mut self) -> Option<char> {
+                    let c = self.rest().chars().next()?;
+                    self.start += c.len_utf8();
+                    Some(c)
+                }
+
+                /// Consume the next char from the back, removing it from the contained String
+                fn pop_back(&mut self) -> Option<char> {
+                    if self.start < self.s.len() { self.s.pop() } else { None }
+                }
+
+                /// Build an item from a char and its byte position
+                #[inline]
+                fn make_item($offset: usize, $c: char) -> $item {
+                    $make_item
+                }
+
                 /// Consume this struct and return the contained String
                 pub fn into_inner(self) -> String {
                     self.s
                 }
 
+                /// Take the contained String out, transform it with `f`, and continue iterating over
+                /// the result
+                ///
+                /// The front position is kept if it is still a char boundary of the new String, and
+                /// otherwise iteration starts over from the beginning. Chars consumed from the back
+                /// have already been removed from the String that `f` receives.
+                ///
+                /// # Example
+                ///
+                /// ```rust
+                /// # use owned_chars::OwnedCharsExt;
+                /// let mut chars = String::from("key=value").into_chars();
+                /// chars.eat("key=");
+                /// let mut chars = chars.map_inner(|s| s.to_uppercase());
+                /// assert_eq!(chars.as_str(), "VALUE");
+                /// ```
+                pub fn map_inner<F: FnOnce(String) -> String>(self, f: F) -> Self {
+                    let offset = self.byte_offset();
+                    let s = f(self.s);
+                    let start = if s.is_char_boundary(offset) { offset } else { 0 };
+                    let end = s.len();
+                    Self::from_range(s, start, end)
+                }
+
+                /// Consume this struct and return the contained String together with the current
+                /// byte position (see `byte_offset`)
+                ///
+                /// Chars consumed from the back have been removed from the String, so they are not
+                /// part of either.
+                pub fn into_parts(self) -> (String, usize) {
+                    let offset = self.byte_offset();
+                    (self.s, offset)
+                }
+
+                /// Consume this struct and return only the part of the contained String that has not
+                /// been yielded yet (the same text as `as_str`)
+                ///
+                /// The existing allocation is reused by shifting the remaining bytes to the front, so
+                /// this takes one memmove of the remaining length and never allocates.
+                ///
+                /// # Example
+                ///
+                /// ```rust
+                /// # use owned_chars::OwnedCharsExt;
+                /// let mut chars = String::from("[abc]").into_chars();
+                /// chars.next();
+                /// chars.next_back();
+                /// assert_eq!(chars.into_remaining(), "abc");
+                /// ```
+                pub fn into_remaining(self) -> String {
+                    let (start, end) = (self.byte_offset(), self.end_offset());
+                    let mut s = self.s;
+                    keep_range(&mut s, start, end);
+                    s
+                }
+
+                /// Consume this struct and split the part of the contained String that has not been
+                /// yielded yet after its first `n` chars
+                ///
+                /// If fewer than `n` chars remain, the second String is empty. The first String
+                /// reuses the existing allocation, as with `into_remaining`.
+                ///
+                /// # Example
+                ///
+                /// ```rust
+                /// # use owned_chars::OwnedCharsExt;
+                /// let mut chars = String::from("#héllo wörld").into_chars();
+                /// chars.next();
+                /// assert_eq!(chars.split_at_char(5), (String::from("héllo"), String::from(" wörld")));
+                /// ```
+                pub fn split_at_char(self, n: usize) -> (String, String) {
+                    let (start, end) = (self.byte_offset(), self.end_offset());
+                    let mid = self.char_to_byte_offset(n).unwrap_or(end);
+                    let mut s = self.s;
+                    let tail = s[mid..end].to_owned();
+                    keep_range(&mut s, start, mid);
+                    (s, tail)
+                }
+
+                /// Returns the byte position in the contained String (not relative to `as_str`)
+                /// that is `n` chars after the current position, or None if fewer than `n` chars
+                /// remain, without moving the iterator
+                ///
+                /// The result can be passed directly to `seek_to`.
+                ///
+                /// # Example
+                ///
+                /// ```rust
+                /// # use owned_chars::OwnedCharsExt;
+                /// let mut chars = String::from("héllo").into_chars();
+                /// chars.next();
+                /// assert_eq!(chars.char_to_byte_offset(0), Some(1));
+                /// assert_eq!(chars.char_to_byte_offset(2), Some(4));
+                /// assert_eq!(chars.char_to_byte_offset(4), Some(6));
+                /// assert_eq!(chars.char_to_byte_offset(5), None);
+                /// ```
+                pub fn char_to_byte_offset(&self, n: usize) -> Option<usize> {
+                    let rest = self.rest();
+                    let i = rest.char_indices().map(|(i, _)| i).chain(Some(rest.len())).nth(n)?;
+                    Some(self.byte_offset() + i)
+                }
+
+                /// Returns a copy of the part of the contained String that has not been yielded yet,
+                /// leaving the iterator untouched (see `into_remaining` for the consuming version)
+                pub fn clone_remaining(&self) -> String {
+                    self.rest().to_owned()
+                }
+
+                /// Append the part of the contained String that has not been yielded yet to `buf`,
+                /// leaving the iterator exhausted
+                ///
+                /// This is a single `push_str`, rather than pushing char by char as collecting
+                /// through the `Iterator` impl would.
+                ///
+                /// # Example
+                ///
+                /// ```rust
+                /// # use owned_chars::OwnedCharsExt;
+                /// let mut chars = String::from("> quoted").into_chars();
+                /// chars.eat("> ");
+                /// let mut buf = String::from("text: ");
+                /// chars.collect_remaining_into(&mut buf);
+                /// assert_eq!(buf, "text: quoted");
+                /// assert_eq!(chars.next(), None);
+                /// ```
+                pub fn collect_remaining_into(&mut self, buf: &mut String) {
+                    buf.push_str(self.rest());
+                    let end = self.end_offset();
+                    self.set_range(end, end);
+                }
+
+                /// Consume this struct and return only the part of the contained String that has
+                /// already been consumed from the front (the same text as `consumed_str`)
+                ///
+                /// The existing allocation is reused.
+                ///
+                /// # Example
+                ///
+                /// ```rust
+                /// # use owned_chars::OwnedCharsExt;
+                /// let mut chars = String::from("key: value").into_chars();
+                /// chars.eat_while(|c| c != ':');
+                /// assert_eq!(chars.into_consumed(), "key");
+                /// ```
+                pub fn into_consumed(self) -> String {
+                    let offset = self.byte_offset();
+                    let mut s = self.s;
+                    s.truncate(offset);
+                    s
+                }
+
+                /// Consume this struct and split the contained String at the current position,
+                /// returning the consumed and remaining parts
+                ///
+                /// Chars consumed from the back have been removed from the String and are in
+                /// neither part. Only the remaining part is copied; the consumed part keeps the
+                /// original allocation.
+                ///
+                /// # Example
+                ///
+                /// ```rust
+                /// # use owned_chars::OwnedCharsExt;
+                /// let mut chars = String::from("GET /index.html").into_chars();
+                /// chars.eat_while(|c| c != ' ');
+                /// let (method, rest) = chars.split_at_cursor();
+                /// assert_eq!(method, "GET");
+                /// assert_eq!(rest, " /index.html");
+                /// ```
+                pub fn split_at_cursor(self) -> (String, String) {
+                    let offset = self.byte_offset();
+                    let mut s = self.s;
+                    let rest = s.split_off(offset);
+                    (s, rest)
+                }
+
                 /// Returns a string slice of contained `String`.
                 ///
+                /// This includes the chars consumed from the front, but not those consumed from the
+                /// back, which are removed from the String as they are yielded.
+                ///
                 /// # Example
                 ///
                 /// ```rust
@@ -81,8 +831,9 @@ mod structs {
                 /// chars.next();
                 /// assert_eq!(chars.get_inner(), "abc");
                 /// chars.next();
-                /// chars.next();
                 /// assert_eq!(chars.get_inner(), "abc");
+                /// chars.next_back();
+                /// assert_eq!(chars.get_inner(), "ab");
                 /// ```
                 pub fn get_inner(&self) -> &str {
                     &self.s
@@ -90,68 +841,2168 @@ mod structs {
 
                 /// Borrow the contained String
                 pub fn as_str(&self) -> &str {
-                    self.i.as_str()
+                    self.rest()
                 }
-            }
-
-            impl Iterator for $owned_struct {
-                type Item = $item;
 
-                fn next(&mut self) -> Option<$item> {
-                    self.i.next()
+                /// Returns the bytes of the input that has not been yielded yet (the same text as
+                /// `as_str`)
+                pub fn as_bytes(&self) -> &[u8] {
+                    self.rest().as_bytes()
                 }
-                fn count(self) -> usize {
-                    self.i.count()
+
+                /// Returns true if there are no chars left to yield
+                ///
+                /// # Example
+                ///
+                /// ```rust
+                /// # use owned_chars::OwnedCharsExt;
+                /// let mut chars = String::from("é").into_chars();
+                /// assert!(!chars.is_empty());
+                /// assert_eq!(chars.remaining_len(), 2);
+                /// chars.next();
+                /// assert!(chars.is_empty());
+                /// assert_eq!(chars.remaining_len(), 0);
+                /// ```
+                pub fn is_empty(&self) -> bool {
+                    self.rest().is_empty()
                 }
-                fn size_hint(&self) -> (usize, Option<usize>) {
-                    self.i.size_hint()
+
+                /// Returns the length in bytes of the input that has not been yielded yet
+                pub fn remaining_len(&self) -> usize {
+                    self.rest().len()
                 }
-                fn last(self) -> Option<$item> {
-                    self.i.last()
+
+                /// Returns the number of bytes that have been consumed from the front, i.e. the
+                /// byte position of the next char in the contained String.
+                ///
+                /// # Example
+                ///
+                /// ```rust
+                /// # use owned_chars::{OwnedChars, OwnedCharsExt};
+                /// let mut chars: OwnedChars = String::from("héllo").into_chars();
+                /// assert_eq!(chars.byte_offset(), 0);
+                /// chars.next();
+                /// chars.next();
+                /// assert_eq!(chars.byte_offset(), 3);
+                /// chars.next_back();
+                /// assert_eq!(chars.byte_offset(), 3);
+                /// ```
+                pub fn byte_offset(&self) -> usize {
+                    self.start
                 }
-            }
 
-            impl DoubleEndedIterator for $owned_struct {
-                fn next_back(&mut self) -> Option<$item> {
-                    self.i.next_back()
+                /// Returns the byte position of the next char in the contained String (this is the
+                /// same as `byte_offset`, named like `CharIndices::offset` in std)
+                ///
+                /// # Example
+                ///
+                /// ```rust
+                /// # use owned_chars::OwnedCharsExt;
+                /// let mut chars = String::from("héllo").into_char_indices();
+                /// chars.next();
+                /// assert_eq!(chars.offset(), 1);
+                /// chars.by_ref().count();
+                /// assert_eq!(chars.offset(), 6);
+                /// ```
+                pub fn offset(&self) -> usize {
+                    self.byte_offset()
                 }
-            }
 
-            impl FusedIterator for $owned_struct {}
-        };
-    }
+                /// Returns the part of the contained String that has already been consumed from
+                /// the front (the complement of `as_str` in `get_inner`)
+                ///
+                /// # Example
+                ///
+                /// ```rust
+                /// # use owned_chars::{OwnedChars, OwnedCharsExt};
+                /// let mut chars: OwnedChars = String::from("héllo").into_chars();
+                /// chars.next();
+                /// chars.next();
+                /// assert_eq!(chars.consumed_str(), "hé");
+                /// assert_eq!(chars.as_str(), "llo");
+                /// ```
+                pub fn consumed_str(&self) -> &str {
+                    &self.s[..self.byte_offset()]
+                }
 
-    impls!(OwnedChars, Chars, chars, char);
-    impls!(OwnedCharIndices, CharIndices, char_indices, (usize, char));
-}
+                /// Rewind the iterator to the beginning of the contained String
+                ///
+                /// Chars consumed from the back have been removed from the String and are not
+                /// restored.
+                ///
+                /// # Example
+                ///
+                /// ```rust
+                /// # use owned_chars::OwnedCharsExt;
+                /// let mut chars = String::from("abc").into_chars();
+                /// assert_eq!(chars.by_ref().count(), 3);
+                /// chars.reset();
+                /// assert_eq!(chars.collect::<String>(), "abc");
+                /// ```
+                pub fn reset(&mut self) {
+                    let end = self.s.len();
+                    self.set_range(0, end);
+                }
+
+                /// Drop the part of the contained String that has already been consumed from the
+                /// front, shifting the rest down and releasing the spare memory
+                ///
+                /// The shift is one memmove of the remaining length; releasing the memory may
+                /// reallocate.
+                ///
+                /// Afterwards, byte positions (such as `byte_offset` and the indices yielded by
+                /// `OwnedCharIndices`) are relative to the new, shorter String. Earlier checkpoints
+                /// are no longer valid.
+                ///
+                /// # Example
+                ///
+                /// ```rust
+                /// # use owned_chars::OwnedCharsExt;
+                /// let mut chars = String::from("header;body").into_char_indices();
+                /// chars.eat("header;");
+                /// chars.truncate_consumed();
+                /// assert_eq!(chars.get_inner(), "body");
+                /// assert_eq!(chars.next(), Some((0, 'b')));
+                /// ```
+                pub fn truncate_consumed(&mut self) {
+                    let (start, end) = (self.byte_offset(), self.end_offset());
+                    keep_range(&mut self.s, start, end);
+                    self.s.shrink_to_fit();
+                    self.start = 0;
+                }
+
+                /// Wrap this iterator in an adapter that drops the consumed prefix of the contained
+                /// String whenever it reaches `threshold` bytes (see `Reclaiming`)
+                pub fn reclaim_after(self, threshold: usize) -> Reclaiming<Self> {
+                    Reclaiming::new(self, threshold)
+                }
+
+                /// Check whether the input that has not been yielded yet is ASCII, and if it is,
+                /// wrap this iterator in an adapter whose `size_hint` is exact and whose `count`
+                /// takes constant time (see `KnownAscii`); otherwise give the iterator back
+                ///
+                /// The check scans the remaining input once. Use `OwnedAsciiChars` instead if the
+                /// String has to be ASCII.
+                ///
+                /// # Example
+                ///
+                /// ```rust
+                /// # use owned_chars::OwnedCharsExt;
+                /// let chars = String::from("plain").into_chars().check_ascii().unwrap();
+                /// assert_eq!(chars.size_hint(), (5, Some(5)));
+                ///
+                /// let chars = String::from("naïve").into_chars().check_ascii().unwrap_err();
+                /// assert_eq!(chars.size_hint(), (2, Some(6)));
+                /// ```
+                pub fn check_ascii(self) -> Result<KnownAscii<Self>, Self> {
+                    if self.rest().is_ascii() {
+                        Ok(KnownAscii { iter: self })
+                    } else {
+                        Err(self)
+                    }
+                }
+
+                /// Save the current position so that it can be restored later with `rollback`
+                ///
+                /// # Example
+                ///
+                /// ```rust
+                /// # use owned_chars::OwnedCharsExt;
+                /// let mut chars = String::from("0x1f").into_chars();
+                /// let start = chars.checkpoint();
+                /// if !(chars.eat("0") && chars.eat("b")) {
+                ///     chars.rollback(start);
+                /// }
+                /// assert_eq!(chars.as_str(), "0x1f");
+                /// ```
+                pub fn checkpoint(&self) -> Checkpoint {
+                    Checkpoint { start: self.byte_offset() }
+                }
+
+                /// Restore a front position saved with `checkpoint`, which may be before or after
+                /// the current position
+                ///
+                /// Chars consumed from the back since the checkpoint was taken have been removed
+                /// from the String and stay consumed.
+                ///
+                /// # Panics
+                ///
+                /// Panics if the checkpoint does not fit the remaining String, i.e. if it is after
+                /// the end of `as_str()` or not on a char boundary.
+                pub fn rollback(&mut self, checkpoint: Checkpoint) {
+                    let end = self.end_offset();
+                    self.set_range(checkpoint.start, end);
+                }
+
+                /// Move the front of the iterator to the given byte position, which may be before
+                /// or after the current position
+                ///
+                /// Chars consumed from the back stay consumed, so the position must be a char
+                /// boundary no later than the end of `as_str()`; otherwise an error is returned and
+                /// the iterator is left untouched.
+                ///
+                /// # Example
+                ///
+                /// ```rust
+                /// # use owned_chars::OwnedCharsExt;
+                /// let mut chars = String::from("héllo").into_char_indices();
+                /// chars.seek_to(3).unwrap();
+                /// assert_eq!(chars.next(), Some((3, 'l')));
+                /// chars.seek_to(1).unwrap();
+                /// assert_eq!(chars.next(), Some((1, 'é')));
+                /// assert!(chars.seek_to(2).is_err());
+                /// ```
+                pub fn seek_to(&mut self, offset: usize) -> Result<(), NotCharBoundary> {
+                    let end = self.end_offset();
+                    if offset <= end && self.s.is_char_boundary(offset) {
+                        self.set_range(offset, end);
+                        Ok(())
+                    } else {
+                        Err(NotCharBoundary::new(offset))
+                    }
+                }
+
+                /// Move the back of the iterator to the given byte position, truncating the
+                /// contained String there
+                ///
+                /// This is the counterpart of `seek_to`, but since chars consumed from the back are
+                /// removed from the String, the back can only move backward: the position must be a
+                /// char boundary between `byte_offset()` and the end of `as_str()`; otherwise an
+                /// error is returned and the iterator is left untouched.
+                ///
+                /// # Example
+                ///
+                /// ```rust
+                /// # use owned_chars::OwnedCharsExt;
+                /// let mut chars = String::from("héllo").into_char_indices();
+                /// chars.seek_back_to(4).unwrap();
+                /// assert_eq!(chars.next_back(), Some((3, 'l')));
+                /// assert!(chars.seek_back_to(6).is_err());
+                /// assert!(chars.seek_back_to(2).is_err());
+                /// ```
+                pub fn seek_back_to(&mut self, offset: usize) -> Result<(), NotCharBoundary> {
+                    let start = self.byte_offset();
+                    let end = self.end_offset();
+                    if offset >= start && offset <= end && self.s.is_char_boundary(offset) {
+                        self.set_range(start, offset);
+                        Ok(())
+                    } else {
+                        Err(NotCharBoundary::new(offset))
+                    }
+                }
+
+                /// Like `eat_while`, but returns the consumed chars as a new String
+                ///
+                /// # Example
+                ///
+                /// ```rust
+                /// # use owned_chars::OwnedCharsExt;
+                /// let mut chars = String::from("42px").into_chars();
+                /// let digits: String = chars.take_while_collect(|c| c.is_ascii_digit());
+                /// assert_eq!(digits, "42");
+                /// assert_eq!(chars.as_str(), "px");
+                /// ```
+                pub fn take_while_collect<F: FnMut(char) -> bool>(&mut self, f: F) -> String {
+                    self.eat_while(f).to_owned()
+                }
+
+                /// Consume the rest of the current line and return it (without the line ending), or
+                /// None if there is no input left
+                ///
+                /// Lines end with `\n` or `\r\n`, as for `str::lines`; a `\r` that is not followed
+                /// by `\n` is part of the line. The iterator is left at the start of the following
+                /// line.
+                ///
+                /// # Example
+                ///
+                /// ```rust
+                /// # use owned_chars::OwnedCharsExt;
+                /// let mut chars = String::from("3\r\n1 2 3\n").into_chars();
+                /// assert_eq!(chars.next_line(), Some("3"));
+                /// assert_eq!(chars.next_line(), Some("1 2 3"));
+                /// assert_eq!(chars.next_line(), None);
+                /// ```
+                pub fn next_line(&mut self) -> Option<&str> {
+                    if self.is_empty() {
+                        return None;
+                    }
+                    let (start, end) = (self.byte_offset(), self.end_offset());
+                    let line = match self.rest().find('\n') {
+                        Some(i) => {
+                            self.set_range(start + i + 1, end);
+                            let line = &self.s[start..start + i];
+                            line.strip_suffix('\r').unwrap_or(line)
+                        }
+                        None => {
+                            self.set_range(end, end);
+                            &self.s[start..end]
+                        }
+                    };
+                    Some(line)
+                }
+
+                /// Skip any whitespace, then consume the following run of non-whitespace chars and
+                /// return it, or None if there is nothing but whitespace left
+                ///
+                /// # Example
+                ///
+                /// ```rust
+                /// # use owned_chars::OwnedCharsExt;
+                /// let mut chars = String::from(" 10  -7\n").into_chars();
+                /// assert_eq!(chars.next_word(), Some("10"));
+                /// assert_eq!(chars.next_word(), Some("-7"));
+                /// assert_eq!(chars.next_word(), None);
+                /// ```
+                pub fn next_word(&mut self) -> Option<&str> {
+                    self.skip_whitespace();
+                    let word = self.eat_while(|c| !c.is_whitespace());
+                    if word.is_empty() { None } else { Some(word) }
+                }
+
+                /// Advance over any whitespace (as defined by `char::is_whitespace`), returning the
+                /// number of chars skipped
+                ///
+                /// # Example
+                ///
+                /// ```rust
+                /// # use owned_chars::OwnedCharsExt;
+                /// let mut chars = String::from(" \t\u{a0}x").into_chars();
+                /// assert_eq!(chars.skip_whitespace(), 3);
+                /// assert_eq!(chars.next(), Some('x'));
+                /// ```
+                pub fn skip_whitespace(&mut self) -> usize {
+                    // ASCII whitespace (including U+000B, unlike `u8::is_ascii_whitespace`) can be
+                    // skipped without decoding
+                    let ascii = self.rest().bytes()
+                        .take_while(|b| matches!(b, b'\t'..=b'\r' | b' '))
+                        .count();
+                    let (start, end) = (self.byte_offset(), self.end_offset());
+                    self.set_range(start + ascii, end);
+                    ascii + self.eat_while(char::is_whitespace).chars().count()
+                }
+
+                /// Advance over any ASCII whitespace (as defined by `char::is_ascii_whitespace`),
+                /// returning the number of chars skipped
+                pub fn skip_ascii_whitespace(&mut self) -> usize {
+                    // ASCII chars are one byte each
+                    self.eat_while(|c| c.is_ascii_whitespace()).len()
+                }
+
+                /// Search the remaining input for `needle`, returning the byte position of the first
+                /// occurrence in the contained String (not relative to `as_str`), without moving the
+                /// iterator
+                ///
+                /// This is `str::find` with a char, which looks for the last byte of the char's
+                /// UTF-8 encoding instead of decoding every char. The result can be passed directly
+                /// to `seek_to`.
+                ///
+                /// # Example
+                ///
+                /// ```rust
+                /// # use owned_chars::OwnedCharsExt;
+                /// let mut chars = String::from("a,b,c").into_chars();
+                /// chars.next();
+                /// chars.next();
+                /// assert_eq!(chars.find_char(','), Some(3));
+                /// chars.seek_to(3).unwrap();
+                /// assert_eq!(chars.as_str(), ",c");
+                /// ```
+                pub fn find_char(&self, needle: char) -> Option<usize> {
+                    self.rest().find(needle).map(|i| self.byte_offset() + i)
+                }
+
+                /// Search the remaining input for `needle`, returning the byte position of the first
+                /// occurrence in the contained String (not relative to `as_str`), without moving the
+                /// iterator
+                pub fn find_str(&self, needle: &str) -> Option<usize> {
+                    self.rest().find(needle).map(|i| self.byte_offset() + i)
+                }
+
+                /// Search the remaining input from the back for `needle`, returning the byte
+                /// position of the last occurrence in the contained String (not relative to
+                /// `as_str`), without moving the iterator
+                ///
+                /// Like `find_char`, this uses std's search, and the result can be passed directly
+                /// to `seek_to` or `seek_back_to`.
+                ///
+                /// # Example
+                ///
+                /// ```rust
+                /// # use owned_chars::OwnedCharsExt;
+                /// let mut chars = String::from("archive.tar.gz").into_chars();
+                /// let dot = chars.rfind_char('.').unwrap();
+                /// assert_eq!(dot, 11);
+                /// chars.seek_back_to(dot).unwrap();
+                /// assert_eq!(chars.as_str(), "archive.tar");
+                /// assert_eq!(chars.rfind_char('.'), Some(7));
+                /// ```
+                pub fn rfind_char(&self, needle: char) -> Option<usize> {
+                    self.rest().rfind(needle).map(|i| self.byte_offset() + i)
+                }
+
+                /// Search the remaining input from the back for `needle`, returning the byte
+                /// position of the last occurrence in the contained String (not relative to
+                /// `as_str`), without moving the iterator
+                pub fn rfind_str(&self, needle: &str) -> Option<usize> {
+                    self.rest().rfind(needle).map(|i| self.byte_offset() + i)
+                }
+
+                /// Advance to the next occurrence of `needle` (or to the end, if there is none),
+                /// returning the slice of the contained String that was skipped
+                ///
+                /// The search is done by `find_char`, so this avoids decoding the skipped chars one
+                /// by one as a loop over `next` would.
+                ///
+                /// # Example
+                ///
+                /// ```rust
+                /// # use owned_chars::OwnedCharsExt;
+                /// let mut chars = String::from("// comment\ncode").into_chars();
+                /// assert_eq!(chars.skip_until('\n'), "// comment");
+                /// assert_eq!(chars.next(), Some('\n'));
+                /// assert_eq!(chars.skip_until('\n'), "code");
+                /// ```
+                pub fn skip_until(&mut self, needle: char) -> &str {
+                    let (start, end) = (self.byte_offset(), self.end_offset());
+                    let found = self.find_char(needle).unwrap_or(end);
+                    self.set_range(found, end);
+                    &self.s[start..found]
+                }
+
+                /// Returns the number of `\n` chars in the remaining input, without moving the
+                /// iterator
+                ///
+                /// This counts bytes rather than decoding chars.
+                pub fn count_newlines(&self) -> usize {
+                    self.rest().bytes().filter(|&b| b == b'\n').count()
+                }
+
+                /// Give back a char that was just yielded, so that `next` returns it again
+                ///
+                /// This rewinds the front of the iterator over `c`, which must be the char
+                /// immediately before the current position. If it isn't, nothing happens and false is
+                /// returned. Several chars can be put back by calling this repeatedly, in reverse
+                /// order.
+                ///
+                /// # Example
+                ///
+                /// ```rust
+                /// # use owned_chars::OwnedCharsExt;
+                /// let mut chars = String::from("12+").into_chars();
+                /// let mut digits = String::new();
+                /// while let Some(c) = chars.next() {
+                ///     if !c.is_ascii_digit() {
+                ///         assert!(chars.put_back(c));
+                ///         break;
+                ///     }
+                ///     digits.push(c);
+                /// }
+                /// assert_eq!(digits, "12");
+                /// assert_eq!(chars.next(), Some('+'));
+                /// ```
+                pub fn put_back(&mut self, c: char) -> bool {
+                    if self.consumed_str().ends_with(c) {
+                        let (start, end) = (self.byte_offset(), self.end_offset());
+                        self.set_range(start - c.len_utf8(), end);
+                        true
+                    } else {
+                        false
+                    }
+                }
+
+                /// Move the front of the iterator back by one char and return that char's item (or
+                /// None if already at the start of the contained String)
+                ///
+                /// This walks back over consumed chars, unlike `next_back`, which consumes from the
+                /// other end.
+                ///
+                /// # Example
+                ///
+                /// ```rust
+                /// # use owned_chars::OwnedCharsExt;
+                /// let mut chars = String::from("héllo").into_char_indices();
+                /// chars.nth(2);
+                /// assert_eq!(chars.prev(), Some((3, 'l')));
+                /// assert_eq!(chars.prev(), Some((1, 'é')));
+                /// assert_eq!(chars.prev(), Some((0, 'h')));
+                /// assert_eq!(chars.prev(), None);
+                /// assert_eq!(chars.next(), Some((0, 'h')));
+                /// ```
+                pub fn prev(&mut self) -> Option<$item> {
+                    let c = self.consumed_str().chars().next_back()?;
+                    let (start, end) = (self.byte_offset() - c.len_utf8(), self.end_offset());
+                    self.set_range(start, end);
+                    Some(Self::make_item(start, c))
+                }
+
+                /// Move the front of the iterator to the given byte position (this is the same as
+                /// `seek_to`, named to pair with `set_position_unchecked`)
+                pub fn set_position(&mut self, offset: usize) -> Result<(), NotCharBoundary> {
+                    self.seek_to(offset)
+                }
+
+                /// Move the front of the iterator to the given byte position without checking it
+                ///
+                /// # Safety
+                ///
+                /// `offset` must be on a char boundary of the contained String and no later than
+                /// the end of `as_str()` (i.e. `set_position` would have succeeded).
+                #[cfg(not(feature = "safe"))]
+                pub unsafe fn set_position_unchecked(&mut self, offset: usize) {
+                    self.start = offset;
+                }
+
+                /// Move the front of the iterator to the given byte position without checking it
+                ///
+                /// # Safety
+                ///
+                /// `offset` must be on a char boundary of the contained String and no later than
+                /// the end of `as_str()` (i.e. `set_position` would have succeeded). With the
+                /// `safe` feature this is not relied upon: invalid positions panic instead.
+                #[cfg(feature = "safe")]
+                #[allow(unsafe_code)]
+                pub unsafe fn set_position_unchecked(&mut self, offset: usize) {
+                    let end = self.end_offset();
+                    self.set_range(offset, end);
+                }
+
+                /// Skip up to `n` chars from the front, returning the number of chars skipped (which
+                /// is less than `n` only if the iterator ran out)
+                ///
+                /// Unlike `skip`, this keeps the iterator (and its accessors) around. Runs of ASCII
+                /// are skipped without decoding.
+                ///
+                /// # Example
+                ///
+                /// ```rust
+                /// # use owned_chars::OwnedCharsExt;
+                /// let mut chars = String::from("héllo").into_chars();
+                /// assert_eq!(chars.advance_chars(2), 2);
+                /// assert_eq!(chars.as_str(), "llo");
+                /// assert_eq!(chars.advance_chars(5), 3);
+                /// assert_eq!(chars.next(), None);
+                /// ```
+                pub fn advance_chars(&mut self, n: usize) -> usize {
+                    let ascii = self.rest().bytes().take(n).take_while(u8::is_ascii).count();
+                    let (start, end) = (self.byte_offset(), self.end_offset());
+                    self.set_range(start + ascii, end);
+
+                    let mut skipped = ascii;
+                    while skipped < n && self.pop_front().is_some() {
+                        skipped += 1;
+                    }
+                    skipped
+                }
+
+                /// Skip up to `n` chars from the back, returning the number of chars skipped (which
+                /// is less than `n` only if the iterator ran out)
+                ///
+                /// Runs of ASCII are skipped without decoding.
+                pub fn advance_back_chars(&mut self, n: usize) -> usize {
+                    let ascii = self.rest().bytes().rev().take(n).take_while(u8::is_ascii).count();
+                    let (start, end) = (self.byte_offset(), self.end_offset());
+                    self.set_range(start, end - ascii);
+
+                    let mut skipped = ascii;
+                    while skipped < n && self.pop_back().is_some() {
+                        skipped += 1;
+                    }
+                    skipped
+                }
+
+                /// Returns the item that `next` would return, without consuming it
+                ///
+                /// # Example
+                ///
+                /// ```rust
+                /// # use owned_chars::OwnedCharsExt;
+                /// let mut chars = String::from("héllo").into_char_indices();
+                /// chars.next();
+                /// assert_eq!(chars.peek(), Some((1, 'é')));
+                /// assert_eq!(chars.next(), Some((1, 'é')));
+                /// ```
+                pub fn peek(&self) -> Option<$item> {
+                    self.peek_nth(0)
+                }
+
+                /// Returns the item that `nth(n)` would return, without consuming anything
+                ///
+                /// This scans the remaining input, so it is meant for small amounts of lookahead.
+                ///
+                /// # Example
+                ///
+                /// ```rust
+                /// # use owned_chars::OwnedCharsExt;
+                /// let chars = String::from("<=>").into_chars();
+                /// assert_eq!(chars.peek_nth(1), Some('='));
+                /// assert_eq!(chars.peek_nth(3), None);
+                /// assert_eq!(chars.as_str(), "<=>");
+                /// ```
+                pub fn peek_nth(&self, n: usize) -> Option<$item> {
+                    let mut i = self.rest().chars();
+                    for _ in 0..n {
+                        i.next()?;
+                    }
+                    let offset = self.byte_offset() + (self.rest().len() - i.as_str().len());
+                    i.next().map(|c| Self::make_item(offset, c))
+                }
+
+                /// Consume and return the next item if its char matches `f`; otherwise leave the
+                /// iterator alone and return None
+                ///
+                /// # Example
+                ///
+                /// ```rust
+                /// # use owned_chars::OwnedCharsExt;
+                /// let mut chars = String::from("-5").into_char_indices();
+                /// assert_eq!(chars.next_if(|c| c == '+'), None);
+                /// assert_eq!(chars.next_if_eq('-'), Some((0, '-')));
+                /// assert_eq!(chars.next_if(|c| c.is_ascii_digit()), Some((1, '5')));
+                /// ```
+                pub fn next_if<F: FnOnce(char) -> bool>(&mut self, f: F) -> Option<$item> {
+                    match self.rest().chars().next() {
+                        Some(c) if f(c) => self.next(),
+                        _ => None,
+                    }
+                }
+
+                /// Consume and return the next item if its char is `expected`; otherwise leave the
+                /// iterator alone and return None
+                pub fn next_if_eq(&mut self, expected: char) -> Option<$item> {
+                    self.next_if(|c| c == expected)
+                }
+
+                /// If the remaining input starts with `prefix`, advance past it and return true;
+                /// otherwise leave the iterator alone and return false
+                ///
+                /// # Example
+                ///
+                /// ```rust
+                /// # use owned_chars::OwnedCharsExt;
+                /// let mut chars = String::from("let x").into_chars();
+                /// assert!(!chars.eat("fn"));
+                /// assert!(chars.eat("let"));
+                /// assert_eq!(chars.as_str(), " x");
+                /// ```
+                pub fn eat(&mut self, prefix: &str) -> bool {
+                    if self.rest().starts_with(prefix) {
+                        let (start, end) = (self.byte_offset(), self.end_offset());
+                        self.set_range(start + prefix.len(), end);
+                        true
+                    } else {
+                        false
+                    }
+                }
+
+                /// If the remaining input starts with `prefix`, advance the front past it and return
+                /// true (this is the same as `eat`, named to pair with `strip_suffix`)
+                pub fn strip_prefix(&mut self, prefix: &str) -> bool {
+                    self.eat(prefix)
+                }
+
+                /// If the remaining input ends with `suffix`, move the back of the iterator before it
+                /// (as if it had been consumed with `next_back`) and return true; otherwise leave the
+                /// iterator alone and return false
+                ///
+                /// # Example
+                ///
+                /// ```rust
+                /// # use owned_chars::OwnedCharsExt;
+                /// let mut chars = String::from("\"quoted\"").into_chars();
+                /// if chars.strip_prefix("\"") && chars.strip_suffix("\"") {
+                ///     assert_eq!(chars.as_str(), "quoted");
+                /// }
+                /// assert_eq!(chars.next_back(), Some('d'));
+                /// ```
+                pub fn strip_suffix(&mut self, suffix: &str) -> bool {
+                    if self.rest().ends_with(suffix) {
+                        let (start, end) = (self.byte_offset(), self.end_offset());
+                        self.set_range(start, end - suffix.len());
+                        true
+                    } else {
+                        false
+                    }
+                }
+
+                /// Advance over the chars that match `f`, stopping at the first one that doesn't, and
+                /// return the slice of the contained String that was skipped
+                ///
+                /// # Example
+                ///
+                /// ```rust
+                /// # use owned_chars::OwnedCharsExt;
+                /// let mut chars = String::from("width=80").into_chars();
+                /// assert_eq!(chars.eat_while(char::is_alphabetic), "width");
+                /// assert_eq!(chars.eat_while(char::is_alphabetic), "");
+                /// assert_eq!(chars.as_str(), "=80");
+                /// ```
+                pub fn eat_while<F: FnMut(char) -> bool>(&mut self, mut f: F) -> &str {
+                    let start = self.byte_offset();
+                    let end = self.end_offset();
+                    let len = {
+                        let rest = self.rest();
+                        rest.find(|c| !f(c)).unwrap_or(rest.len())
+                    };
+                    self.set_range(start + len, end);
+                    &self.s[start..start + len]
+                }
+
+                /// Convert into an iterator over the bytes of the contained String, positioned at the
+                /// same place
+                ///
+                /// # Example
+                ///
+                /// ```rust
+                /// # use owned_chars::OwnedCharsExt;
+                /// let mut chars = String::from("é!").into_chars();
+                /// chars.next();
+                /// let mut bytes = chars.into_bytes();
+                /// assert_eq!(bytes.next(), Some(b'!'));
+                /// ```
+                pub fn into_bytes(self) -> OwnedBytes {
+                    let (start, end) = (self.byte_offset(), self.end_offset());
+                    OwnedBytes::from_range(self.s, start, end)
+                }
+
+                /// Wrap this iterator in an adapter that keeps track of the number of chars
+                /// yielded (see `Counted::chars_consumed`)
+                pub fn counted(self) -> Counted<Self> {
+                    Counted::new(self)
+                }
+
+                /// Wrap this iterator in an adapter that yields at most `n` chars, keeping the
+                /// accessors available (see `TakeChars`)
+                pub fn take_chars(self, n: usize) -> TakeChars<Self> {
+                    TakeChars::new(self, n)
+                }
+
+                /// Wrap this iterator in an adapter that skips the first `n` chars, keeping the
+                /// accessors available (see `SkipChars`)
+                pub fn skip_chars(self, n: usize) -> SkipChars<Self> {
+                    SkipChars::new(self, n)
+                }
+
+                /// Wrap this iterator in an adapter whose `peek` returns a reference, keeping the
+                /// accessors available (see `OwnedPeekable`)
+                pub fn peekable_owned(self) -> OwnedPeekable<Self> {
+                    OwnedPeekable::new(self)
+                }
+
+                /// Wrap this iterator in an adapter that only yields the items matching `pred`,
+                /// keeping the accessors available (see `OwnedFilter`)
+                pub fn filter_owned<P: FnMut(&$item) -> bool>(self, pred: P) -> OwnedFilter<Self, P> {
+                    OwnedFilter::new(self, pred)
+                }
+
+                /// Wrap this iterator in an adapter that tags each item with whether it is the
+                /// first, last or only one (see `WithPosition`)
+                pub fn with_position(self) -> WithPosition<Self> {
+                    WithPosition::new(self)
+                }
+
+                /// Wrap this iterator in an adapter that keeps track of the line and column of the
+                /// next char (see `LineCounted::line_col`)
+                pub fn line_counted(self) -> LineCounted<Self> {
+                    LineCounted::new(self)
+                }
+
+                /// Wrap this iterator in an adapter that skips chars equal to the previous one,
+                /// keeping the accessors available (see `Dedup`)
+                pub fn dedup_owned(self) -> Dedup<Self, fn(char) -> char, char> {
+                    Dedup::new(self, |c| c)
+                }
+
+                /// Wrap this iterator in an adapter that skips chars with the same key as the
+                /// previous one, keeping the accessors available (see `Dedup`)
+                pub fn dedup_by_key_owned<K, F>(self, key: F) -> Dedup<Self, F, K>
+                    where K: PartialEq, F: FnMut(char) -> K
+                {
+                    Dedup::new(self, key)
+                }
+
+                /// Split the remaining input into at most `n` non-empty byte ranges of roughly
+                /// equal length, each starting and ending on a char boundary
+                ///
+                /// The ranges are positions in the contained String (like `byte_offset`) and cover
+                /// the remaining input in order. This is the chunking used by `par_map_chunks`.
+                ///
+                /// # Example
+                ///
+                /// ```rust
+                /// # use owned_chars::OwnedCharsExt;
+                /// let chars = String::from("ééé").into_chars();
+                /// assert_eq!(chars.char_chunks(2), [0..4, 4..6]);
+                /// assert_eq!(chars.char_chunks(10), [0..2, 2..4, 4..6]);
+                /// ```
+                pub fn char_chunks(&self, n: usize) -> Vec<Range<usize>> {
+                    let (start, end) = (self.byte_offset(), self.end_offset());
+                    let n = n.max(1);
+                    let mut chunks = Vec::with_capacity(n);
+                    let mut chunk_start = start;
+                    for k in 1..=n {
+                        let mut chunk_end = start + (end - start) * k / n;
+                        while !self.s.is_char_boundary(chunk_end) {
+                            chunk_end += 1;
+                        }
+                        if chunk_end > chunk_start {
+                            chunks.push(chunk_start..chunk_end);
+                            chunk_start = chunk_end;
+                        }
+                    }
+                    chunks
+                }
+
+                /// Run `f` on up to `n` chunks of the remaining input in parallel, returning the
+                /// results in order
+                ///
+                /// `f` gets the byte position of each chunk in the contained String together with
+                /// its text. The chunks are those given by `char_chunks`. The iterator itself is
+                /// not moved.
+                ///
+                /// No more threads are spawned than `std::thread::available_parallelism` suggests,
+                /// however large `n` is: the chunks are split into that many runs of consecutive
+                /// chunks, one per thread. If only one thread would be used, everything runs on
+                /// the current thread.
+                ///
+                /// # Example
+                ///
+                /// ```rust
+                /// # use owned_chars::OwnedCharsExt;
+                /// let chars = String::from("one two three four").into_chars();
+                /// let starts = chars.par_map_chunks(3, |offset, chunk| (offset, chunk.len()));
+                /// assert_eq!(starts, [(0, 6), (6, 6), (12, 6)]);
+                /// ```
+                #[cfg(feature = "std")]
+                pub fn par_map_chunks<T, F>(&self, n: usize, f: F) -> Vec<T>
+                    where T: Send, F: Fn(usize, &str) -> T + Sync
+                {
+                    let chunks = self.char_chunks(n);
+                    let threads = ::std::thread::available_parallelism()
+                        .map_or(1, |n| n.get())
+                        .min(chunks.len());
+                    let run = |ranges: &[Range<usize>]| {
+                        ranges.iter().map(|range| f(range.start, &self.s[range.clone()])).collect::<Vec<_>>()
+                    };
+                    if threads <= 1 {
+                        return run(&chunks);
+                    }
+                    let run = &run;
+                    ::std::thread::scope(|scope| {
+                        let threads = chunks.chunks(chunks.len().div_ceil(threads))
+                            .map(|ranges| scope.spawn(move || run(ranges)))
+                            .collect::<Vec<_>>();
+                        threads.into_iter()
+                            .flat_map(|thread| thread.join().unwrap_or_else(|e| ::std::panic::resume_unwind(e)))
+                            .collect()
+                    })
+                }
+
+                /// Fold the remaining chars in up to `n` chunks in parallel, then combine the
+                /// results of the chunks in order with `reduce`
+                ///
+                /// Each chunk is folded starting from a clone of `init` (so it should be an identity
+                /// for `reduce`), which is also the result if there is no input left. The iterator
+                /// itself is not moved. The threads are used as for `par_map_chunks`.
+                ///
+                /// # Example
+                ///
+                /// ```rust
+                /// # use owned_chars::OwnedCharsExt;
+                /// let chars = String::from("Hello, Wörld!").into_chars();
+                /// let upper = chars.par_fold_chars(4, 0, |n, c| n + c.is_uppercase() as usize,
+                ///                                  |a, b| a + b);
+                /// assert_eq!(upper, 2);
+                /// ```
+                #[cfg(feature = "std")]
+                pub fn par_fold_chars<T, F, R>(&self, n: usize, init: T, fold: F, reduce: R) -> T
+                    where T: Clone + Send + Sync, F: Fn(T, char) -> T + Sync, R: FnMut(T, T) -> T
+                {
+                    self.par_map_chunks(n, |_, chunk| chunk.chars().fold(init.clone(), &fold))
+                        .into_iter()
+                        .reduce(reduce)
+                        .unwrap_or(init)
+                }
+            }
+
+            impl OwnedPeekable<$owned_struct> {
+                /// Returns a reference to the next item, without consuming it
+                pub fn peek(&mut self) -> Option<&$item> {
+                    self.peek_with($owned_struct::peek)
+                }
+            }
+
+            impl Iterator for WithPosition<$owned_struct> {
+                type Item = (Position, $item);
+
+                fn next(&mut self) -> Option<(Position, $item)> {
+                    let item = self.iter.next()?;
+                    let first = !self.front;
+                    self.front = true;
+                    Some((Position::new(first, self.iter.is_empty() && !self.back), item))
+                }
+                fn size_hint(&self) -> (usize, Option<usize>) {
+                    self.iter.size_hint()
+                }
+            }
+
+            impl DoubleEndedIterator for WithPosition<$owned_struct> {
+                fn next_back(&mut self) -> Option<(Position, $item)> {
+                    let item = self.iter.next_back()?;
+                    let last = !self.back;
+                    self.back = true;
+                    Some((Position::new(self.iter.is_empty() && !self.front, last), item))
+                }
+            }
+
+            iter_traits!(WithPosition<$owned_struct>, (Position, $item), [FusedIterator]);
+
+            /// Shows the position and the start of the remaining input, rather than the whole
+            /// String
+            impl fmt::Debug for $owned_struct {
+                fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                    f.debug_struct(stringify!($owned_struct))
+                        .field("byte_offset", &self.byte_offset())
+                        .field("remaining_len", &self.remaining_len())
+                        .field("len", &self.s.len())
+                        .field("rest", &Truncated(self.rest()))
+                        .finish()
+                }
+            }
+
+            impl<K: PartialEq, F: FnMut(char) -> K> Iterator for Dedup<$owned_struct, F, K> {
+                type Item = $item;
+
+                fn next(&mut self) -> Option<$item> {
+                    loop {
+                        let key = (self.key)(self.iter.rest().chars().next()?);
+                        let item = self.iter.next();
+                        if self.last.as_ref() != Some(&key) {
+                            self.last = Some(key);
+                            return item;
+                        }
+                    }
+                }
+                fn size_hint(&self) -> (usize, Option<usize>) {
+                    let (lower, upper) = self.iter.size_hint();
+                    (lower.min(1), upper)
+                }
+            }
+
+            impl<K: PartialEq, F: FnMut(char) -> K> FusedIterator for Dedup<$owned_struct, F, K> {}
+
+            impl Iterator for Reclaiming<$owned_struct> {
+                type Item = $item;
+
+                fn next(&mut self) -> Option<$item> {
+                    let offset = self.iter.byte_offset();
+                    if offset >= self.threshold && offset > 0 {
+                        self.iter.truncate_consumed();
+                        self.reclaimed += offset;
+                    }
+                    self.iter.next()
+                }
+                fn size_hint(&self) -> (usize, Option<usize>) {
+                    self.iter.size_hint()
+                }
+            }
+
+            impl DoubleEndedIterator for Reclaiming<$owned_struct> {
+                fn next_back(&mut self) -> Option<$item> {
+                    self.iter.next_back()
+                }
+            }
+
+            iter_traits!(Reclaiming<$owned_struct>, $item, [FusedIterator]);
+
+            impl Reclaiming<$owned_struct> {
+                /// Wrap this adapter in one that keeps track of the line and column of the next
+                /// char, across reclamations (see `LineCounted::line_col`)
+                pub fn line_counted(self) -> LineCounted<Self> {
+                    LineCounted::new(self)
+                }
+            }
+
+            line_counted_impls!($owned_struct, $item);
+            line_counted_impls!(Reclaiming<$owned_struct>, $item);
+
+            impl KnownAscii<$owned_struct> {
+                /// Returns the byte position in the contained String that is `n` chars after the
+                /// current position, or None if fewer than `n` chars remain (see
+                /// `char_to_byte_offset` on the wrapped iterator; here every char is one byte)
+                pub fn char_to_byte_offset(&self, n: usize) -> Option<usize> {
+                    if n <= self.iter.remaining_len() {
+                        Some(self.iter.byte_offset() + n)
+                    } else {
+                        None
+                    }
+                }
+            }
+
+            impl Iterator for KnownAscii<$owned_struct> {
+                type Item = $item;
+
+                fn next(&mut self) -> Option<$item> {
+                    self.iter.next()
+                }
+                fn size_hint(&self) -> (usize, Option<usize>) {
+                    let len = self.iter.remaining_len();
+                    (len, Some(len))
+                }
+                fn count(self) -> usize {
+                    self.iter.remaining_len()
+                }
+                fn nth(&mut self, n: usize) -> Option<$item> {
+                    self.iter.nth(n)
+                }
+                fn last(self) -> Option<$item> {
+                    self.iter.last()
+                }
+            }
+
+            impl DoubleEndedIterator for KnownAscii<$owned_struct> {
+                fn next_back(&mut self) -> Option<$item> {
+                    self.iter.next_back()
+                }
+            }
+
+            iter_traits!(KnownAscii<$owned_struct>, $item, [ExactSizeIterator, FusedIterator]);
+
+            impl TakeChars<$owned_struct> {
+                /// Returns the part of the contained String that this adapter has not yielded yet
+                /// (at most as many chars as are left to take)
+                pub fn as_str(&self) -> &str {
+                    take_str(self.iter.rest(), self.remaining())
+                }
+
+                /// Returns the bytes of the input that this adapter has not yielded yet
+                pub fn as_bytes(&self) -> &[u8] {
+                    self.as_str().as_bytes()
+                }
+
+                /// Returns true if this adapter has no chars left to yield
+                pub fn is_empty(&self) -> bool {
+                    self.as_str().is_empty()
+                }
+
+                /// Returns the length in bytes of the input that this adapter has not yielded yet
+                pub fn remaining_len(&self) -> usize {
+                    self.as_str().len()
+                }
+
+                /// Returns the byte position of the next char in the contained String
+                pub fn byte_offset(&self) -> usize {
+                    self.iter.byte_offset()
+                }
+
+                /// Returns the next item without consuming it, or None if there are no chars left
+                /// to take
+                pub fn peek(&self) -> Option<$item> {
+                    if self.remaining() > 0 { self.iter.peek() } else { None }
+                }
+
+                /// Returns a string slice of contained `String`
+                pub fn get_inner(&self) -> &str {
+                    self.iter.get_inner()
+                }
+
+                /// Consume the adapter and return the contained String
+                pub fn into_inner(self) -> String {
+                    self.into_wrapped().into_inner()
+                }
+            }
+
+            impl SkipChars<$owned_struct> {
+                /// Returns the part of the contained String that this adapter has not yielded yet
+                /// (without the chars that are still to be skipped)
+                pub fn as_str(&self) -> &str {
+                    skip_str(self.iter.rest(), self.remaining())
+                }
+
+                /// Returns the bytes of the input that this adapter has not yielded yet
+                pub fn as_bytes(&self) -> &[u8] {
+                    self.as_str().as_bytes()
+                }
+
+                /// Returns true if this adapter has no chars left to yield
+                pub fn is_empty(&self) -> bool {
+                    self.as_str().is_empty()
+                }
+
+                /// Returns the length in bytes of the input that this adapter has not yielded yet
+                pub fn remaining_len(&self) -> usize {
+                    self.as_str().len()
+                }
+
+                /// Returns the byte position of the next char this adapter will yield, in the
+                /// contained String
+                pub fn byte_offset(&self) -> usize {
+                    self.iter.end_offset() - self.remaining_len()
+                }
+
+                /// Returns the next item this adapter will yield without consuming anything
+                pub fn peek(&self) -> Option<$item> {
+                    self.iter.peek_nth(self.remaining())
+                }
+
+                /// Returns a string slice of contained `String`
+                pub fn get_inner(&self) -> &str {
+                    self.iter.get_inner()
+                }
+
+                /// Consume the adapter and return the contained String
+                pub fn into_inner(self) -> String {
+                    self.into_wrapped().into_inner()
+                }
+            }
+
+            impl Iterator for $owned_struct {
+                type Item = $item;
+
+                fn next(&mut self) -> Option<$item> {
+                    let offset = self.byte_offset();
+                    self.pop_front().map(|c| Self::make_item(offset, c))
+                }
+                fn count(self) -> usize {
+                    // in ASCII every byte is a char
+                    let rest = self.rest();
+                    if rest.is_ascii() { rest.len() } else { rest.chars().count() }
+                }
+                fn nth(&mut self, n: usize) -> Option<$item> {
+                    self.advance_chars(n);
+                    self.next()
+                }
+                fn size_hint(&self) -> (usize, Option<usize>) {
+                    self.rest().chars().size_hint()
+                }
+                fn last(mut self) -> Option<$item> {
+                    let c = self.pop_back();
+                    let offset = self.byte_offset() + self.rest().len();
+                    c.map(|c| Self::make_item(offset, c))
+                }
+            }
+        };
+    }
+
+    impls!(OwnedChars, char, |_offset, c| c);
+    iter_traits!(OwnedChars, char, [DoubleEndedIterator, FusedIterator]);
+
+    impls!(OwnedCharIndices, (usize, char), |offset, c| (offset, c));
+    iter_traits!(OwnedCharIndices, (usize, char), [DoubleEndedIterator, FusedIterator]);
+
+    impls!(OwnedCharRanges, (Range<usize>, char), |offset, c| (offset..offset + c.len_utf8(), c));
+    iter_traits!(OwnedCharRanges, (Range<usize>, char), [DoubleEndedIterator, FusedIterator]);
+
+    impls!(OwnedCodePoints, u32, |_offset, c| c as u32);
+    iter_traits!(OwnedCodePoints, u32, [DoubleEndedIterator, FusedIterator]);
+
+    impls!(OwnedCodePointIndices, (usize, u32), |offset, c| (offset, c as u32));
+    iter_traits!(OwnedCodePointIndices, (usize, u32), [DoubleEndedIterator, FusedIterator]);
+
+    /// Iterator over the bytes of a string (the string is owned by the iterator)
+    pub struct OwnedBytes {
+        s: String,
+        #[cfg(not(feature = "safe"))]
+        i: slice::Iter<'static, u8>,
+        /// byte range of the part that has not been consumed
+        #[cfg(feature = "safe")]
+        i: Range<usize>,
+    }
+
+    impl OwnedBytes {
+        /// Create Self from a String, moving the String into Self
+        pub fn from_string(s: String) -> Self {
+            let end = s.len();
+            Self::from_range(s, 0, end)
+        }
+
+        /// Create Self iterating over the given byte range of a String (panics if the range is out
+        /// of bounds)
+        #[cfg(not(feature = "safe"))]
+        fn from_range(s: String, start: usize, end: usize) -> Self {
+            let i = unsafe {
+                // The iterator borrows the String's heap buffer, which does not move when the
+                // String does. We need the transmute to "widen" its lifetime into 'static so that
+                // it can be stored next to the String.
+                //
+                // The struct fields are private, so users can't observe this fake static
+                // lifetime. Code within this module must never destructure the struct because it
+                // risks losing track of the real lifetime!
+                transmute::<slice::Iter<u8>, slice::Iter<'static, u8>>(s.as_bytes()[start..end].iter())
+            };
+            OwnedBytes { s, i }
+        }
+
+        /// Create Self iterating over the given byte range of a String (panics if the range is out
+        /// of bounds)
+        #[cfg(feature = "safe")]
+        fn from_range(s: String, start: usize, end: usize) -> Self {
+            assert!(start <= end && end <= s.len(), "range out of bounds");
+            OwnedBytes { s, i: start..end }
+        }
+
+        /// Returns a byte yielded by the inner iterator
+        #[cfg(not(feature = "safe"))]
+        fn byte(&self, b: &u8) -> u8 {
+            *b
+        }
+
+        /// Returns the byte at a position yielded by the inner iterator
+        #[cfg(feature = "safe")]
+        fn byte(&self, k: usize) -> u8 {
+            self.s.as_bytes()[k]
+        }
+
+        /// Consume this struct and return the contained String
+        pub fn into_inner(self) -> String {
+            self.s
+        }
+
+        /// Returns a string slice of contained `String`
+        pub fn get_inner(&self) -> &str {
+            &self.s
+        }
+
+        /// Returns the bytes that have not been yielded yet
+        pub fn as_bytes(&self) -> &[u8] {
+            #[cfg(not(feature = "safe"))]
+            let bytes = self.i.as_slice();
+            #[cfg(feature = "safe")]
+            let bytes = &self.s.as_bytes()[self.i.clone()];
+            bytes
+        }
+
+        /// Returns the part of the contained String that has not been yielded yet
+        ///
+        /// Returns an error if a position (front or back) is in the middle of a char; `as_bytes`
+        /// works at any position.
+        pub fn as_str(&self) -> Result<&str, NotCharBoundary> {
+            let (start, end) = self.check_range()?;
+            Ok(&self.s[start..end])
+        }
+
+        /// Returns the number of bytes that have been consumed from the front
+        pub fn byte_offset(&self) -> usize {
+            #[cfg(not(feature = "safe"))]
+            let offset = self.i.as_slice().as_ptr() as usize - self.s.as_ptr() as usize;
+            #[cfg(feature = "safe")]
+            let offset = self.i.start;
+            offset
+        }
+
+        /// Convert back into an iterator over chars at the same position
+        ///
+        /// Returns an error if a position (front or back) is in the middle of a char.
+        ///
+        /// # Example
+        ///
+        /// ```rust
+        /// # use owned_chars::OwnedBytes;
+        /// let mut bytes = OwnedBytes::from_string(String::from("é!"));
+        /// bytes.next();
+        /// assert!(bytes.into_chars().is_err());
+        ///
+        /// let mut bytes = OwnedBytes::from_string(String::from("é!"));
+        /// bytes.nth(1);
+        /// assert_eq!(bytes.into_chars().unwrap().next(), Some('!'));
+        /// ```
+        pub fn into_chars(self) -> Result<OwnedChars, NotCharBoundary> {
+            let (start, end) = self.check_range()?;
+            Ok(OwnedChars::from_range(self.s, start, end))
+        }
+
+        /// Convert back into an iterator over chars and their indices at the same position
+        ///
+        /// Returns an error if a position (front or back) is in the middle of a char.
+        pub fn into_char_indices(self) -> Result<OwnedCharIndices, NotCharBoundary> {
+            let (start, end) = self.check_range()?;
+            Ok(OwnedCharIndices::from_range(self.s, start, end))
+        }
+
+        /// Returns the remaining byte range if both ends are on char boundaries
+        fn check_range(&self) -> Result<(usize, usize), NotCharBoundary> {
+            let start = self.byte_offset();
+            let end = start + self.i.len();
+            match (self.s.is_char_boundary(start), self.s.is_char_boundary(end)) {
+                (true, true) => Ok((start, end)),
+                (false, _) => Err(NotCharBoundary::new(start)),
+                (true, false) => Err(NotCharBoundary::new(end)),
+            }
+        }
+    }
+
+    /// Shows the position and the start of the remaining input, rather than the whole String
+    impl fmt::Debug for OwnedBytes {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            let rest = self.as_bytes();
+            f.debug_struct("OwnedBytes")
+                .field("byte_offset", &self.byte_offset())
+                .field("remaining_len", &rest.len())
+                .field("len", &self.s.len())
+                .field("rest", &&rest[..rest.len().min(DEBUG_CHARS)])
+                .finish()
+        }
+    }
+
+    impl Iterator for OwnedBytes {
+        type Item = u8;
+
+        fn next(&mut self) -> Option<u8> {
+            self.i.next().map(|k| self.byte(k))
+        }
+        fn count(self) -> usize {
+            self.i.count()
+        }
+        fn size_hint(&self) -> (usize, Option<usize>) {
+            self.i.size_hint()
+        }
+        fn nth(&mut self, n: usize) -> Option<u8> {
+            self.i.nth(n).map(|k| self.byte(k))
+        }
+        fn last(mut self) -> Option<u8> {
+            self.i.next_back().map(|k| self.byte(k))
+        }
+    }
+
+    impl DoubleEndedIterator for OwnedBytes {
+        fn next_back(&mut self) -> Option<u8> {
+            self.i.next_back().map(|k| self.byte(k))
+        }
+    }
+
+    iter_traits!(OwnedBytes, u8, [ExactSizeIterator, FusedIterator]);
+
+    /// Converts std's owning iterator, picking up where it left off
+    #[cfg(feature = "nightly-std")]
+    impl From<::alloc::string::IntoChars> for OwnedChars {
+        fn from(chars: ::alloc::string::IntoChars) -> Self {
+            OwnedChars::from_string(chars.into_string())
+        }
+    }
+
+    /// Converts to std's owning iterator, keeping only the chars that have not been yielded yet
+    #[cfg(feature = "nightly-std")]
+    impl From<OwnedChars> for ::alloc::string::IntoChars {
+        fn from(chars: OwnedChars) -> Self {
+            String::into_chars(chars.into_remaining())
+        }
+    }
+}
+
+pub use structs::*;
+
+#[test]
+#[allow(unstable_name_collisions)]
+fn chars() {
+    let s = String::from("héllo");
+    assert_eq!(s.chars().collect::<Vec<_>>(),
+               s.into_chars().collect::<Vec<_>>());
+}
+
+#[test]
+#[allow(unstable_name_collisions, clippy::iter_skip_next)]
+fn unicode() {
+    let s = String::from("héllo");
+    assert_eq!(Some('é'), s.clone().into_chars().skip(1).next());
+    assert_eq!(Some('l'), s.clone().into_chars().skip(2).next());
+}
+
+#[test]
+fn char_indices() {
+    let s = String::from("héllo");
+    assert_eq!(s.char_indices().collect::<Vec<_>>(),
+               s.into_char_indices().collect::<Vec<_>>());
+}
+
+#[test]
+fn char_ranges() {
+    let s = String::from("héllo wörld");
+    let expected = s.char_indices().map(|(i, c)| (i..i + c.len_utf8(), c)).collect::<Vec<_>>();
+    assert_eq!(expected, s.clone().into_char_ranges().collect::<Vec<_>>());
+    let mut backwards = s.clone().into_char_ranges().rev().collect::<Vec<_>>();
+    backwards.reverse();
+    assert_eq!(expected, backwards);
+}
+
+#[test]
+fn code_points() {
+    let s = String::from("héllo wörld 𝄞");
+    assert_eq!(s.chars().map(u32::from).collect::<Vec<_>>(),
+               s.clone().into_code_points().collect::<Vec<_>>());
+    assert_eq!(s.char_indices().map(|(i, c)| (i, u32::from(c))).rev().collect::<Vec<_>>(),
+               s.clone().into_code_point_indices().rev().collect::<Vec<_>>());
+}
+
+#[test]
+fn char_indices_back() {
+    let s = String::from("héllo wörld");
+    let mut std_iter = s.char_indices();
+    let mut owned = s.clone().into_char_indices();
+    while let Some(item) = std_iter.next_back() {
+        assert_eq!(Some(item), owned.next_back());
+        assert_eq!(std_iter.next(), owned.next());
+    }
+    assert_eq!(None, owned.next_back());
+    assert_eq!(s.char_indices().last(), s.clone().into_char_indices().last());
+}
+
+#[test]
+fn take_skip() {
+    let s = String::from("héllo");
+    for n in 0..7 {
+        let mut take = s.clone().into_char_indices().take_chars(n);
+        let mut skip = s.clone().into_char_indices().skip_chars(n);
+        assert_eq!(s.chars().take(n).collect::<String>(), take.as_str());
+        assert_eq!(s.chars().skip(n).collect::<String>(), skip.as_str());
+        assert_eq!(s.char_indices().take(n).next(), take.next());
+        assert_eq!(s.char_indices().nth(n), skip.next());
+        assert_eq!(s.char_indices().take(n).skip(1).collect::<Vec<_>>(), take.by_ref().collect::<Vec<_>>());
+        assert_eq!(s.char_indices().skip(n + 1).collect::<Vec<_>>(), skip.by_ref().collect::<Vec<_>>());
+        assert!(take.is_empty() && skip.is_empty());
+        assert_eq!(s, skip.into_inner());
+    }
+
+    // the accessors respect the limit
+    let take = s.clone().into_char_indices().take_chars(0);
+    assert!(take.is_empty());
+    assert_eq!((0, 0, None), (take.remaining_len(), take.as_bytes().len(), take.peek()));
+    assert_eq!(s, take.get_inner());
+    let skip = s.clone().into_char_indices().skip_chars(2);
+    assert_eq!((3, "llo".as_bytes(), Some((3, 'l'))), (skip.byte_offset(), skip.as_bytes(), skip.peek()));
+    assert_eq!(3, skip.remaining_len());
+    assert_eq!("héllo", skip.into_wrapped().as_str());
+}
+
+#[test]
+fn peekable() {
+    let s = String::from("hé");
+    let mut std_iter = s.char_indices().peekable();
+    let mut owned = s.clone().into_char_indices().peekable_owned();
+    assert_eq!(std_iter.peek(), owned.peek());
+    assert_eq!(std_iter.next(), owned.next());
+    assert_eq!(std_iter.peek(), owned.peek());
+    assert_eq!("é", owned.as_str());
+    assert_eq!(std_iter.next_back(), owned.next_back());
+    assert_eq!(std_iter.peek(), owned.peek());
+    assert_eq!(std_iter.next(), owned.next());
+}
+
+#[test]
+fn filter() {
+    let s = String::from("h\u{e9}l\u{2028}lo");
+    let keep = |&(_, c): &(usize, char)| c.is_alphabetic();
+    let mut owned = s.clone().into_char_indices().filter_owned(keep);
+    assert_eq!(s.char_indices().rfind(keep), owned.next_back());
+    assert_eq!(s.char_indices().filter(keep).take(4).collect::<Vec<_>>(), owned.by_ref().collect::<Vec<_>>());
+    assert_eq!("", owned.as_str());
+    // the 'o' yielded from the back was removed from the String
+    assert_eq!(s[..s.len() - 1], owned.into_inner().into_inner());
+}
+
+#[test]
+fn with_position() {
+    use Position::*;
 
-pub use structs::*;
+    let positions = |s: &str| OwnedCharsExt::into_chars(s.to_string()).with_position().map(|(p, _)| p).collect::<Vec<_>>();
+    assert_eq!(Vec::<Position>::new(), positions(""));
+    assert_eq!(vec![Only], positions("é"));
+    assert_eq!(vec![First, Last], positions("ab"));
+    assert_eq!(vec![First, Middle, Middle, Last], positions("abcd"));
+
+    let mut owned = String::from("abc").into_char_indices().with_position();
+    assert_eq!(Some((Last, (2, 'c'))), owned.next_back());
+    assert_eq!(Some((First, (0, 'a'))), owned.next());
+    assert_eq!("b", owned.as_str());
+    assert_eq!(Some((Middle, (1, 'b'))), owned.next_back());
+    assert_eq!(None, owned.next());
+}
 
 #[test]
-fn chars() {
-    let s = String::from("héllo");
-    assert_eq!(s.chars().collect::<Vec<_>>(),
-               s.into_chars().collect::<Vec<_>>());
+fn debug() {
+    let mut chars = OwnedCharsExt::into_chars("é".repeat(40));
+    chars.next();
+    chars.next_back();
+    assert_eq!(format!("OwnedChars {{ byte_offset: 2, remaining_len: 76, len: 78, rest: {:?}... }}",
+                       "é".repeat(32)),
+               format!("{:?}", chars));
+
+    let mut bytes = String::from("abc").into_owned_bytes();
+    bytes.next();
+    assert_eq!("OwnedBytes { byte_offset: 1, remaining_len: 2, len: 3, rest: [98, 99] }",
+               format!("{:?}", bytes));
 }
 
 #[test]
-fn unicode() {
-    let s = String::from("héllo");
-    assert_eq!(Some('é'), s.clone().into_chars().skip(1).next());
-    assert_eq!(Some('l'), s.clone().into_chars().skip(2).next());
+fn zip_chars() {
+    for &(a, b) in &[("", ""), ("a", ""), ("héllo", "hèllo"), ("€", "€uro")] {
+        let mut zip = OwnedZipChars::new(a.to_string(), b.to_string());
+        assert_eq!(a.chars().zip(b.chars()).collect::<Vec<_>>(), zip.by_ref().collect::<Vec<_>>());
+        let n = a.chars().count().min(b.chars().count());
+        assert_eq!((&*a.chars().skip(n).collect::<String>(), &*b.chars().skip(n).collect::<String>()),
+                   zip.as_strs());
+        assert_eq!((a.to_string(), b.to_string()), zip.into_inner());
+    }
 }
 
 #[test]
-fn char_indices() {
-    let s = String::from("héllo");
-    assert_eq!(s.char_indices().collect::<Vec<_>>(),
-               s.into_char_indices().collect::<Vec<_>>());
+fn intersperse() {
+    for s in &["", "a", "ab", "héllo"] {
+        for sep in &["", ",", "€ "] {
+            let mut owned = OwnedCharsExt::into_chars(s.to_string()).intersperse_owned(*sep);
+            let expected = s.chars().map(String::from).collect::<Vec<_>>().join(sep);
+            let mut yielded = String::new();
+            loop {
+                let (lower, upper) = owned.size_hint();
+                let left = expected.chars().count() - yielded.chars().count();
+                assert!(lower <= left && left <= upper.unwrap());
+                match owned.next() {
+                    Some(c) => yielded.push(c),
+                    None => break,
+                }
+            }
+            assert_eq!(expected, yielded);
+        }
+    }
+}
+
+#[test]
+fn dedup() {
+    let dedup = |s: &str| OwnedCharsExt::into_chars(s.to_string()).dedup_owned().collect::<String>();
+    assert_eq!("", dedup(""));
+    assert_eq!("a", dedup("aaa"));
+    assert_eq!("hélo wörld", dedup("hhéélllo  wörrlldd"));
+
+    let mut owned = String::from("AaBbbC").into_char_indices().dedup_by_key_owned(|c| c.to_ascii_lowercase());
+    assert_eq!(Some((0, 'A')), owned.next());
+    assert_eq!("aBbbC", owned.as_str());
+    assert_eq!(vec![(2, 'B'), (5, 'C')], owned.by_ref().collect::<Vec<_>>());
+    assert_eq!("AaBbbC", owned.into_inner().into_inner());
+}
+
+#[test]
+fn reclaim() {
+    let s = "héllo wörld ".repeat(100);
+    let mut chars = s.clone().into_char_indices().reclaim_after(64);
+    let mut collected = String::new();
+    while let Some((i, c)) = chars.next() {
+        // positions are relative to the reclaimed String, and at most one char past the threshold
+        assert_eq!(Some(c), s[chars.reclaimed_len() + i..].chars().next());
+        assert!(i < 64 + 4);
+        collected.push(c);
+    }
+    assert!(chars.reclaimed_len() > 0);
+    assert_eq!(s, collected);
+}
+
+#[test]
+fn line_counted() {
+    let line_col = |s: &str| {
+        let line_start = s.rfind('\n').map_or(0, |i| i + 1);
+        (s.matches('\n').count() as u32 + 1, s[line_start..].chars().count() as u32 + 1)
+    };
+
+    let s = "héllo\nwörld\n\n€".repeat(20);
+    let mut chars = s.clone().into_char_indices().line_counted();
+    assert_eq!((1, 1), chars.line_col());
+    assert_eq!(Some((0, 'h')), chars.next());
+    assert_eq!((1, 2), chars.line_col());
+    assert_eq!(s.chars().next_back(), chars.next_back().map(|(_, c)| c));
+    while chars.next().is_some() {
+        assert_eq!(line_col(chars.consumed_str()), chars.line_col());
+    }
+
+    // the counters carry on after the consumed part of the String has been dropped
+    let mut chars = s.clone().into_char_indices().reclaim_after(16).line_counted();
+    let mut consumed = String::new();
+    while let Some((_, c)) = chars.next() {
+        consumed.push(c);
+        assert_eq!(line_col(&consumed), chars.line_col());
+    }
+    assert!(chars.reclaimed_len() > 0);
+    assert_eq!((61, 2), chars.line_col());
+}
+
+#[test]
+fn advance() {
+    let s = String::from("ab¢d€fg");
+    for n in 0..9 {
+        let mut std_iter = s.char_indices();
+        let mut owned = s.clone().into_char_indices();
+        assert_eq!(n.min(7), owned.advance_chars(n));
+        if n > 0 {
+            std_iter.nth(n - 1);
+        }
+        assert_eq!(std_iter.as_str(), owned.as_str());
+        assert_eq!(std_iter.next(), owned.next());
+
+        let mut std_iter = s.char_indices();
+        let mut owned = s.clone().into_char_indices();
+        assert_eq!(n.min(7), owned.advance_back_chars(n));
+        if n > 0 {
+            std_iter.nth_back(n - 1);
+        }
+        assert_eq!(std_iter.as_str(), owned.as_str());
+        assert_eq!(std_iter.next_back(), owned.next_back());
+    }
+}
+
+#[test]
+fn eat() {
+    let mut oci = String::from("«ab»").into_char_indices();
+    assert!(oci.eat(""));
+    assert!(!oci.eat("ab"));
+    assert!(oci.eat("«"));
+    assert_eq!(Some((2, 'a')), oci.next());
+    assert_eq!(Some((4, '»')), oci.next_back());
+    assert!(!oci.eat("b»"));
+    assert!(oci.eat("b"));
+    assert_eq!(None, oci.next());
+
+    let mut oc = OwnedCharsExt::into_chars(String::from(" \x0b\u{a0} a\nb\nc"));
+    assert_eq!(4, oc.skip_whitespace());
+    assert_eq!(2, oc.count_newlines());
+    assert_eq!("a", oc.skip_until('\n'));
+    oc.next_back();
+    assert_eq!("\nb\n", oc.skip_until('c'));
+    assert_eq!(0, oc.count_newlines());
+}
+
+#[test]
+fn chunks() {
+    let mut oci = String::from("aé€𝄞bc").into_char_indices();
+    oci.next();
+    oci.next_back();
+    assert_eq!(vec![1..3, 3..6, 6..10, 10..11], oci.char_chunks(4));
+    assert_eq!(vec![1..11], oci.char_chunks(0));
+
+    #[cfg(feature = "std")]
+    {
+        let s = "héllo wörld\n".repeat(100);
+        let oc = OwnedCharsExt::into_chars(s.clone());
+        let chunks = oc.par_map_chunks(7, |offset, chunk| (offset, chunk.to_owned()));
+        assert_eq!(7, chunks.len());
+        assert_eq!(s, chunks.iter().map(|c| &*c.1).collect::<String>());
+        assert!(chunks.iter().all(|&(offset, ref chunk)| s[offset..].starts_with(chunk)));
+        assert_eq!(s.chars().count(), oc.par_fold_chars(7, 0, |n, _| n + 1, |a, b| a + b));
+
+        let oc = OwnedCharsExt::into_chars(String::new());
+        assert_eq!(5, oc.par_fold_chars(3, 5, |n, _| n + 1, |a, b| a + b));
+
+        // one chunk per char, but only as many threads as the machine has, in order
+        let s = "é".repeat(20_000);
+        let oci = s.clone().into_char_indices();
+        let threads = std::sync::Mutex::new(std::collections::HashSet::new());
+        let offsets = oci.par_map_chunks(20_000, |offset, _| {
+            threads.lock().unwrap().insert(std::thread::current().id());
+            offset
+        });
+        assert_eq!((0..20_000).map(|i| 2 * i).collect::<Vec<_>>(), offsets);
+        let parallelism = std::thread::available_parallelism().map_or(1, |n| n.get());
+        assert!(threads.into_inner().unwrap().len() <= parallelism);
+    }
+}
+
+#[test]
+fn ascii_chars() {
+    let s = String::from("GET / HTTP/1.1");
+    let mut oac = s.clone().try_into_ascii_chars().unwrap();
+    assert_eq!(s.chars().collect::<Vec<_>>(), oac.clone().collect::<Vec<_>>());
+    assert_eq!(s.chars().rev().collect::<Vec<_>>(), oac.clone().rev().collect::<Vec<_>>());
+    assert_eq!(Some('/'), oac.nth(4));
+    assert_eq!(5, oac.byte_offset());
+    assert_eq!(9, oac.len());
+    assert_eq!(None, oac.nth(9));
+    assert_eq!(0, oac.len());
+    assert_eq!(s, oac.into_inner());
+
+    let err = OwnedAsciiChars::from_string(String::from("ab€")).unwrap_err();
+    assert_eq!(2, err.offset());
+    assert_eq!("ab€", err.into_string());
+    assert!(String::new().try_into_ascii_chars().is_ok());
+}
+
+#[test]
+fn peek() {
+    let s = String::from("h€llo");
+    let mut oci = s.clone().into_char_indices();
+    oci.next_back();
+    for (n, item) in s.char_indices().take(4).enumerate() {
+        assert_eq!(Some(item), oci.peek_nth(n));
+    }
+    assert_eq!(None, oci.peek_nth(4));
+    assert_eq!(Some((0, 'h')), oci.peek());
+    assert_eq!(s, oci.as_str().to_owned() + "o");
+}
+
+#[test]
+fn bytes() {
+    let s = String::from("aé€");
+    let mut ob = OwnedCharsExt::into_chars(s.clone()).into_bytes();
+    assert_eq!(6, ob.len());
+    assert_eq!(s.bytes().collect::<Vec<_>>(), ob.by_ref().collect::<Vec<_>>());
+
+    let mut oc = s.clone().into_char_indices();
+    oc.next();
+    oc.next_back();
+    let ob = oc.into_bytes();
+    assert_eq!(&s.as_bytes()[1..3], ob.as_bytes());
+    assert_eq!(1, ob.byte_offset());
+    assert_eq!(vec![(1, 'é')], ob.into_char_indices().unwrap().collect::<Vec<_>>());
+
+    let mut ob = OwnedBytes::from_string(s.clone());
+    ob.next_back();
+    assert_eq!(Err(NotCharBoundary::new(5)), ob.into_chars().map(|_| ()));
+}
+
+#[test]
+fn set_position() {
+    let mut oci = String::from("añb").into_char_indices();
+    oci.next_back();
+    assert_eq!(Err(NotCharBoundary::new(2)), oci.set_position(2));
+    assert_eq!(Err(NotCharBoundary::new(4)), oci.set_position(4));
+    assert_eq!(Ok(()), oci.set_position(3));
+    assert_eq!(None, oci.next());
+    unsafe { oci.set_position_unchecked(1) };
+    assert_eq!(vec![(1, 'ñ')], oci.collect::<Vec<_>>());
+}
+
+#[test]
+fn split() {
+    for s in &["", "a", ",", "a,b", ",a,,b,", "ää,,öö", "a<>b<><>c"] {
+        for pat in &["", ",", ",,", "<>", "ö"] {
+            assert_eq!(s.split(pat).collect::<Vec<_>>(),
+                       OwnedSplit::from_string(s.to_string(), pat).collect::<Vec<_>>());
+            assert_eq!(s.split(pat).collect::<Vec<_>>(),
+                       OwnedSplit::from_string(s.to_string(), pat).quoted('"', Some('\\'))
+                           .collect::<Vec<_>>());
+            for n in 0..5 {
+                assert_eq!(s.splitn(n, pat).collect::<Vec<_>>(),
+                           OwnedSplitN::from_string(s.to_string(), n, pat).collect::<Vec<_>>());
+            }
+        }
+    }
+
+    let quoted = OwnedSplit::from_string(String::from(r#"a,"b,c\"",d\,e,"f"#), ",")
+        .quoted('"', Some('\\'));
+    assert_eq!(vec!["a", r#""b,c\"""#, r"d\,e", r#""f"#], quoted.collect::<Vec<_>>());
+    let quoted = OwnedSplitN::from_string(String::from("'a b' c d"), 2, " ").quoted('\'', None);
+    assert_eq!(vec!["'a b'", "c d"], quoted.collect::<Vec<_>>());
+
+    let mut os = OwnedSplitN::from_string(String::from("a,b,c"), 2, ",");
+    assert_eq!(Some("a,b,c"), os.remainder());
+    os.next();
+    assert_eq!(Some("b,c"), os.remainder());
+    os.next();
+    assert_eq!(None, os.remainder());
+    assert_eq!(None, os.into_remainder());
+}
+
+#[test]
+fn find() {
+    for s in &["", "a", "aaa", "abab", "äöäöä", "xaax"] {
+        for pat in &["", "a", "aa", "ab", "äöä"] {
+            assert_eq!(s.match_indices(pat).map(|(i, _)| i).collect::<Vec<_>>(),
+                       OwnedFind::from_string(s.to_string(), pat).collect::<Vec<_>>());
+            let overlapping = (0..s.len() + 1)
+                .filter(|&i| s.is_char_boundary(i) && s[i..].starts_with(pat));
+            assert_eq!(overlapping.collect::<Vec<_>>(),
+                       OwnedFind::from_string(s.to_string(), pat).overlapping().collect::<Vec<_>>());
+        }
+    }
+
+    let mut of = OwnedFind::from_string(String::from("a-b-c"), "-");
+    assert_eq!(Some(1), of.next());
+    assert_eq!("b-c", of.as_str());
+}
+
+#[test]
+fn rfind() {
+    for s in &["", "a", "aaa", "abab", "äöäöä", "xaax"] {
+        for pat in &["", "a", "aa", "ab", "ö"] {
+            let mut chars = s.into_chars();
+            chars.next();
+            chars.next_back();
+            let rest = chars.as_str().to_owned();
+            let expected = rest.rfind(pat).map(|i| chars.byte_offset() + i);
+            assert_eq!(expected, chars.rfind_str(pat));
+            if let Some(c) = pat.chars().next() {
+                assert_eq!(rest.rfind(c).map(|i| chars.byte_offset() + i), chars.rfind_char(c));
+            }
+            if let Some(i) = expected {
+                chars.seek_back_to(i).unwrap();
+                assert_eq!(chars.as_str(), &s[..i][chars.byte_offset()..]);
+            }
+        }
+    }
+
+    let mut chars = "äb".into_chars();
+    chars.next();
+    assert!(chars.seek_back_to(1).is_err());
+    assert!(chars.seek_back_to(4).is_err());
+    assert_eq!(chars.as_str(), "b");
+    chars.seek_back_to(2).unwrap();
+    assert_eq!(chars.as_str(), "");
+}
+
+#[test]
+fn split_at_char() {
+    for s in &["", "a", "héllo", "wörld 𝄞"] {
+        for n in 0..8 {
+            let expected = s.char_indices().nth(n).map(|(i, _)| i).unwrap_or(s.len());
+            let chars = s.into_chars();
+            assert_eq!(chars.char_to_byte_offset(n),
+                       if n <= s.chars().count() { Some(expected) } else { None });
+            if let Ok(ascii) = chars.check_ascii() {
+                assert_eq!(ascii.char_to_byte_offset(n),
+                           if n <= s.chars().count() { Some(expected) } else { None });
+            }
+            let (head, tail) = s.into_chars().split_at_char(n);
+            assert_eq!((&head[..], &tail[..]), s.split_at(expected));
+        }
+    }
+
+    let mut chars = "[äb|cd]".into_chars();
+    chars.next();
+    chars.next_back();
+    assert_eq!(chars.char_to_byte_offset(2), Some(4));
+    assert_eq!(chars.split_at_char(2), (String::from("äb"), String::from("|cd")));
+}
+
+#[test]
+fn lines() {
+    for s in &["", "\n", "a", "a\n", "a\r", "a\r\nb", "\n\na\n\r\n", "a\rb\n\r", "ä\nö\r\n\n"] {
+        let expected = s.lines().collect::<Vec<_>>();
+        assert_eq!(expected, OwnedLines::from_string(s.to_string()).collect::<Vec<_>>());
+        let mut chars = OwnedCharsExt::into_chars(s.to_string());
+        let mut next_lines = vec![];
+        while let Some(line) = chars.next_line() {
+            next_lines.push(line.to_owned());
+        }
+        assert_eq!(expected, next_lines);
+        let mut backwards = OwnedLines::from_string(s.to_string()).rev().collect::<Vec<_>>();
+        backwards.reverse();
+        assert_eq!(expected, backwards);
+        assert_eq!(s.lines().count(), OwnedLines::from_string(s.to_string()).count());
+        assert_eq!(s.lines().last(), OwnedLines::from_string(s.to_string()).last().as_deref());
+        for n in 0..4 {
+            assert_eq!(s.lines().nth(n), OwnedLines::from_string(s.to_string()).nth(n).as_deref());
+        }
+
+        let mut std_iter = s.lines();
+        let mut owned = OwnedLines::from_string(s.to_string());
+        loop {
+            let item = std_iter.next_back();
+            assert_eq!(item, owned.next_back().as_ref().map(|s| &s[..]));
+            assert_eq!(std_iter.next(), owned.next().as_ref().map(|s| &s[..]));
+            if item.is_none() {
+                break;
+            }
+        }
+    }
+
+    // a `\r` is only part of the line ending when `\n` follows it
+    let mut chars = OwnedCharsExt::into_chars(String::from("a\r"));
+    assert_eq!(Some("a\r"), chars.next_line());
+    assert_eq!(None, chars.next_line());
+
+    let mut os = OwnedSplit::from_string(String::from("a b"), " ");
+    assert_eq!("a b", os.as_str());
+    os.by_ref().count();
+    assert_eq!("", os.as_str());
+    assert_eq!("a b", os.get_inner());
+}
+
+#[test]
+fn chars_rev() {
+    for s in &["", "a", "héllo wörld 𝄞€"] {
+        assert_eq!(s.chars().rev().collect::<String>(), s.into_chars_rev().collect::<String>());
+        assert_eq!(*s, s.into_chars_rev().rev().collect::<String>());
+        assert_eq!(s.chars().next(), s.into_chars_rev().last());
+        assert_eq!(s.chars().count(), s.into_chars_rev().count());
+    }
+
+    let mut chars = "ab€d".into_chars_rev();
+    assert_eq!(chars.as_str(), "ab€d");
+    assert_eq!(chars.next(), Some('d'));
+    assert_eq!(chars.next_back(), Some('a'));
+    assert_eq!(chars.as_str(), "b€");
+    assert_eq!(chars.byte_offset(), 5);
+    assert_eq!(chars.get_inner(), "ab€");
+    let mut forward = chars.into_forward();
+    assert_eq!(forward.as_str(), "b€");
+    assert_eq!(forward.next(), Some('b'));
+    let mut chars = forward.into_rev();
+    assert_eq!(chars.next(), Some('€'));
+    assert!(chars.is_empty());
+    assert_eq!(chars.into_inner(), "ab");
+}
+
+#[test]
+fn char_bytes() {
+    for s in &["", "a", "héllo wörld 𝄞€", "\u{10ffff}\0"] {
+        let expected = s.chars().map(|c| c.to_string().into_bytes()).collect::<Vec<_>>();
+        assert_eq!(expected, s.into_char_bytes().collect::<Vec<_>>());
+        let mut backwards = s.into_char_bytes().rev().collect::<Vec<_>>();
+        backwards.reverse();
+        assert_eq!(expected, backwards);
+        assert_eq!(s.chars().count(), s.into_char_bytes().count());
+
+        let mut owned = s.into_char_bytes();
+        let mut joined = Vec::new();
+        while let Some(bytes) = owned.next_bytes() {
+            joined.extend_from_slice(bytes);
+        }
+        assert_eq!(s.as_bytes(), &joined[..]);
+        assert_eq!(s.len(), owned.byte_offset());
+    }
+}
+
+#[cfg(feature = "shell")]
+#[test]
+fn shell_words() {
+    let words = |s: &str| OwnedShellWords::from_string(s.to_owned()).collect::<Vec<_>>();
+    assert_eq!(Vec::<Result<String, _>>::new(), words("  # nothing here\n\t"));
+    assert_eq!(vec![Ok("a".to_owned()), Ok("b c".to_owned()), Ok("d#e".to_owned())],
+               words("a #x\n b\\ c d#e"));
+    assert_eq!(vec![Ok(r#"$ \ " \x"#.to_owned()), Ok("ab".to_owned()), Ok("'\"".to_owned())],
+               words(r#""\$ \\ \" \x" a\
+b "'"'"'"#));
+    assert_eq!(vec![Ok("ü".to_owned()), Err(UnterminatedQuote::new(3))], words("ü \"é"));
+    assert_eq!(vec![Err(UnterminatedQuote::new(1))], words(r"x\"));
+
+    let mut ow = OwnedShellWords::from_string(String::from("one two"));
+    ow.next();
+    assert_eq!(" two", ow.as_str());
+    assert_eq!("one two", ow.into_inner());
+}
+
+#[cfg(feature = "decode")]
+#[test]
+fn utf8_chars() {
+    let inputs: &[&[u8]] = &[b"", b"abc", "héllo€𝄞".as_bytes(), b"\xff", b"a\xc3", b"\xe2\x82",
+                             b"\xf0\x9f\x98a\xed\xa0\x80b", b"\xc0\x80\xf5\xf4\x90\x80\x80"];
+    for &input in inputs {
+        // each error becomes one replacement char, as in from_utf8_lossy
+        let lossy = Utf8Chars::from_vec(input.to_vec())
+            .map(|r| r.unwrap_or('\u{fffd}'))
+            .collect::<String>();
+        assert_eq!(String::from_utf8_lossy(input), lossy);
+
+        // the errors cover exactly the invalid bytes
+        let mut chars = Utf8Chars::from_vec(input.to_vec());
+        let mut pos = 0;
+        while let Some(r) = chars.next() {
+            match r {
+                Ok(c) => pos += c.len_utf8(),
+                Err(e) => {
+                    assert_eq!(pos, e.offset());
+                    assert!(core::str::from_utf8(&input[pos..pos + e.error_len()]).is_err());
+                    pos += e.error_len();
+                }
+            }
+            assert_eq!(pos, chars.byte_offset());
+        }
+        assert_eq!(input.len(), pos);
+        assert_eq!(input, &chars.into_inner()[..]);
+    }
+}
+
+#[cfg(feature = "decode")]
+#[test]
+fn chunked_chars() {
+    let inputs: &[&[u8]] = &[b"", b"abc", "héllo€𝄞".as_bytes(), b"\xff", b"a\xc3", b"\xe2\x82",
+                             b"\xf0\x9f\x98a\xed\xa0\x80b", b"\xc0\x80\xf5\xf4\x90\x80\x80"];
+    for &input in inputs {
+        let expected = Utf8Chars::from_vec(input.to_vec()).collect::<Vec<_>>();
+        // every way of cutting the input into two chunks decodes the same
+        for cut in 0..=input.len() {
+            let mut chars = ChunkedChars::new();
+            chars.push(&input[..cut]);
+            let mut decoded = chars.by_ref().collect::<Vec<_>>();
+            chars.push_vec(input[cut..].to_vec());
+            decoded.extend(chars.by_ref());
+            chars.finish();
+            decoded.extend(chars.by_ref());
+            assert_eq!(expected, decoded);
+            assert_eq!(input.len(), chars.byte_offset());
+            assert!(chars.pending().is_empty());
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn read_chars() {
+    use std::io::{self, Read};
+
+    // returns one byte per read call, with an interruption in between
+    struct Trickle<'a>(&'a [u8], bool);
+    impl<'a> Read for Trickle<'a> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            self.1 = !self.1;
+            if self.1 {
+                return Err(io::ErrorKind::Interrupted.into());
+            }
+            let n = self.0.len().min(buf.len()).min(1);
+            buf[..n].copy_from_slice(&self.0[..n]);
+            self.0 = &self.0[n..];
+            Ok(n)
+        }
+    }
+
+    let inputs: &[&[u8]] = &[b"", b"abc", "héllo wörld €𝄞".as_bytes(), b"\xff", b"a\xc3",
+                             b"\xe2\x82A\xf0\x9d\x84", b"\xc0\xaf\xed\xa0\x80x", b"\xf4\x90\x80\x80"];
+    for &input in inputs {
+        let expected = String::from_utf8_lossy(input);
+        let lossy = ReadChars::lossy(Trickle(input, false)).collect::<io::Result<String>>();
+        assert_eq!(expected, lossy.unwrap());
+
+        let strict = ReadChars::new(Trickle(input, false))
+            .map(|r| r.unwrap_or('\u{fffd}'))
+            .collect::<String>();
+        assert_eq!(expected, strict);
+    }
+
+    let big = "ü".repeat(10000);
+    assert_eq!(big, ReadChars::new(big.as_bytes()).collect::<io::Result<String>>().unwrap());
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn read_lines() {
+    use std::io::{self, BufRead};
+
+    for s in &["", "\n", "a", "a\n", "a\r\nb", "\n\na\n\r\n", "a\rb\n\r", "ä\nö\r\n\n"] {
+        let expected = s.as_bytes().lines().collect::<io::Result<Vec<_>>>().unwrap();
+        let lines = OwnedReadLines::new(s.as_bytes()).collect::<io::Result<Vec<_>>>();
+        assert_eq!(expected, lines.unwrap());
+
+        let mut reused = Vec::new();
+        let mut lines = OwnedReadLines::new(s.as_bytes());
+        while let Some(line) = lines.next_str() {
+            reused.push(line.unwrap().to_owned());
+        }
+        assert_eq!(expected, reused);
+    }
+
+    let mut lines = OwnedReadLines::new(&b"a\n\xff\nb"[..]);
+    assert_eq!("a", lines.next().unwrap().unwrap());
+    assert_eq!(io::ErrorKind::InvalidData, lines.next_str().unwrap().unwrap_err().kind());
+    assert_eq!("b", lines.next_str().unwrap().unwrap());
+    assert!(lines.next().is_none());
+}
+
+#[cfg(feature = "cursor")]
+#[test]
+fn cursor() {
+    use cursor::StrCursor;
+
+    let mut cursor = StrCursor::new(String::from("a\n\"é\\\"\" b"));
+    assert!(cursor.eat_char('a'));
+    assert!(!cursor.eat_char('a'));
+    cursor.skip_whitespace();
+    assert_eq!((2, 1), cursor.line_col());
+
+    // string literal with escapes
+    let start = cursor.mark();
+    assert!(cursor.eat_char('"'));
+    loop {
+        match cursor.bump() {
+            Some('\\') => { cursor.bump(); }
+            Some('"') | None => break,
+            Some(_) => {}
+        }
+    }
+    assert_eq!(2..8, cursor.span_from(start));
+    assert_eq!("\"é\\\"\"", cursor.slice_from(start));
+    assert_eq!(Some(' '), cursor.peek());
+    assert_eq!(Some('b'), cursor.peek_nth(1));
+
+    assert_eq!((2, 6), cursor.line_col());
+
+    cursor.rollback(start);
+    assert_eq!(2, cursor.pos());
+    assert_eq!((2, 1), cursor.line_col());
+    assert_eq!("\"é\\\"\" b", cursor.as_str());
+    assert!(!cursor.is_eof());
+
+    let mut cursor = StrCursor::new(String::from("x\n  y\nz"));
+    assert_eq!("x", cursor.eat_while(|c| c != '\n'));
+    assert_eq!(3, cursor.skip_whitespace());
+    assert_eq!((2, 3), cursor.line_col());
+    assert!(cursor.eat("y\n"));
+    assert_eq!((3, 1), cursor.line_col());
+    assert_eq!("z", cursor.skip_until('!'));
+    assert_eq!((3, 2), cursor.line_col());
+}
+
+#[cfg(feature = "tokens")]
+#[test]
+fn tokens() {
+    use tokens::{Options, Token, TokenKind::*, Tokens};
+
+    let s = String::from("a_b\t 1.x 0x1f 'it''s' \"q\\\" é9");
+    let kinds = |tokens: Tokens| tokens.map(|t| t.kind).collect::<Vec<_>>();
+    assert_eq!(vec![Ident, Whitespace, Number, Punct, Ident, Whitespace, Number, Whitespace,
+                    Punct, Ident, Punct, Punct, Ident, Punct, Whitespace,
+                    Str { terminated: false }],
+               kinds(Tokens::new(s.clone())));
+
+    let options = Options {
+        skip_whitespace: true,
+        ident_extra: String::new(),
+        quotes: String::from("'\""),
+        escape: None,
+    };
+    let mut tokens = Tokens::with_options(s.clone(), options);
+    assert_eq!(Some(Token { kind: Ident, span: 0..1 }), tokens.next());
+    assert_eq!(Some(Token { kind: Punct, span: 1..2 }), tokens.next());
+    assert_eq!(vec![Ident, Number, Punct, Ident, Number, Str { terminated: true },
+                    Str { terminated: true }, Str { terminated: true }, Ident],
+               kinds(tokens));
+
+    let mut tokens = Tokens::new(String::from("x y"));
+    let token = tokens.next().unwrap();
+    assert_eq!("x", tokens.text(&token));
+    assert_eq!(" y", tokens.as_str());
+    assert_eq!("x y", tokens.into_inner());
+}
+
+#[test]
+fn owned_iter_macro() {
+    owned_iter! {
+        struct Codes(for<'a> std::str::Chars<'a>) -> u32 {
+            new: |s| s.chars(),
+            map: |c| c as u32,
+            as_str: |i| i.as_str(),
+            traits: [DoubleEndedIterator],
+        }
+    }
+
+    let mut codes = Codes::from_string(String::from("héllo"));
+    assert_eq!(Some(0x68), codes.next());
+    assert_eq!(Some(0x6f), codes.next_back());
+    assert_eq!("éll", codes.as_str());
+    assert_eq!("héllo", codes.get_inner());
+    assert_eq!(vec![0xe9, 0x6c, 0x6c], codes.collect::<Vec<_>>());
+}
+
+#[test]
+fn owned() {
+    let mut chars = Owned::new(String::from("héllo"), str::chars);
+    assert_eq!(Some('h'), chars.next());
+    assert_eq!(Some('o'), chars.next_back());
+    assert_eq!("héllo", chars.get_inner());
+    assert_eq!(vec!['é', 'l', 'l'], chars.by_ref().collect::<Vec<_>>());
+    assert_eq!("héllo", chars.into_inner());
+
+    let bytes = Owned::new(std::sync::Arc::<str>::from("héllo"), str::bytes);
+    assert_eq!(6, bytes.len());
 }
 
 #[test]
 fn methods() {
     let s = String::from("héllo");
-    let oc = s.clone().into_chars();
+    let oc = OwnedCharsExt::into_chars(s.clone());
     let oci = s.clone().into_char_indices();
     assert_eq!(&s, oc.as_str());
     assert_eq!(&s, oci.as_str());
@@ -159,3 +3010,368 @@ fn methods() {
     assert_eq!(s, oci.into_inner());
 }
 
+#[test]
+fn ascii_fast_paths() {
+    for s in &["", "hello", "héllo", "€uro"] {
+        assert_eq!(s.chars().count(), OwnedCharsExt::into_chars(s.to_string()).count());
+        for n in 0..6 {
+            assert_eq!(s.char_indices().nth(n), s.to_string().into_char_indices().nth(n));
+        }
+    }
+}
+
+#[test]
+fn check_ascii() {
+    for s in &["", "hello", "héllo", "€uro"] {
+        match OwnedCharsExt::into_chars(s.to_string()).check_ascii() {
+            Ok(mut ascii) => {
+                assert!(s.is_ascii());
+                ascii.next();
+                assert_eq!(s.len().saturating_sub(1), ascii.len());
+                assert_eq!(ascii.len(), ascii.clone().count());
+            }
+            Err(chars) => {
+                assert!(!s.is_ascii());
+                assert_eq!(*s, chars.as_str());
+            }
+        }
+    }
+
+    // only the remaining input has to be ASCII
+    let mut chars = String::from("éa").into_char_indices();
+    chars.next();
+    assert_eq!(vec![(2, 'a')], chars.check_ascii().unwrap().collect::<Vec<_>>());
+}
+
+#[test]
+fn into_remaining() {
+    // the allocation is kept
+    let mut oc = OwnedCharsExt::into_chars(String::from("héllo"));
+    oc.next();
+    oc.next_back();
+    let ptr = oc.get_inner().as_ptr();
+    let rest = oc.into_remaining();
+    assert_eq!("éll", rest);
+    assert_eq!(ptr, rest.as_ptr());
+}
+
+#[test]
+fn str_ext() {
+    assert_eq!("héllo", "héllo".into_chars().into_inner());
+    assert_eq!(Some((1, 'é')), "héllo".into_char_indices().nth(1));
+}
+
+#[test]
+fn owned_str() {
+    let s = String::from("héllo");
+    assert_eq!(s.chars().collect::<Vec<_>>(), OwnedStr(s.clone()).into_iter().collect::<Vec<_>>());
+    assert_eq!(s.char_indices().collect::<Vec<_>>(),
+               OwnedStrIndices::from(s.clone()).into_iter().collect::<Vec<_>>());
+}
+
+#[test]
+fn ext_constructors() {
+    let s = String::from("héllo");
+    assert_eq!(s.bytes().collect::<Vec<_>>(), s.clone().into_owned_bytes().collect::<Vec<_>>());
+    assert_eq!(vec!["a", "b"], "a\nb".into_lines().collect::<Vec<_>>());
+    assert_eq!(vec!["a", "b", "c"], String::from("a,b,c").into_split(",").collect::<Vec<_>>());
+    assert_eq!(vec!["a", "b,c"], "a,b,c".into_splitn(2, ",").collect::<Vec<_>>());
+    assert_eq!(vec![1, 3], "a,b,c".into_find(",").collect::<Vec<_>>());
+    #[cfg(feature = "shell")]
+    assert_eq!(Some(Ok(String::from("a b"))), "'a b' c".into_shell_words().next());
+}
+
+#[test]
+fn ext_defaults() {
+    // implementors only provide the two original methods
+    struct Words(&'static str);
+    impl OwnedCharsExt for Words {
+        fn into_chars(self) -> OwnedChars {
+            self.0.into_chars()
+        }
+        fn into_char_indices(self) -> OwnedCharIndices {
+            self.0.into_char_indices()
+        }
+    }
+
+    assert_eq!(vec!["a", "b"], Words("a b").into_split(" ").collect::<Vec<_>>());
+    assert_eq!(Some(0x62), Words("b").into_code_points().next());
+}
+
+#[test]
+fn ext_owners() {
+    let s = String::from("héllo");
+    assert_eq!(s, Box::<str>::from("héllo").into_chars().into_inner());
+    assert_eq!(s, Cow::Borrowed("héllo").into_char_indices().into_inner());
+    assert_eq!(s, Rc::<str>::from("héllo").into_owned_bytes().into_inner());
+    #[cfg(target_has_atomic = "ptr")]
+    assert_eq!(s, Arc::<str>::from("héllo").into_lines().into_inner());
+}
+
+#[test]
+fn offset() {
+    let s = String::from("héllo");
+    let mut oc = s.clone().into_char_indices();
+    oc.nth(1);
+    assert_eq!(s.len() - oc.as_str().len(), oc.offset());
+    assert_eq!(oc.byte_offset(), oc.offset());
+}
+
+#[test]
+fn map_inner() {
+    let mut oc = String::from("héllo").into_char_indices();
+    oc.next();
+    let mut oc = oc.map_inner(|s| s + "!");
+    assert_eq!(Some((1, 'é')), oc.next());
+    let mut oc = oc.map_inner(|s| s.replacen("é", "e", 1));
+    assert_eq!(Some((3, 'l')), oc.next());
+    // the position is no longer a char boundary, so iteration restarts
+    let mut oc = oc.map_inner(|_| String::from("€€"));
+    assert_eq!(Some((0, '€')), oc.next());
+}
+
+#[test]
+fn from_utf16() {
+    let s = String::from("héllo 𝄞");
+    let utf16 = s.encode_utf16().collect::<Vec<_>>();
+    assert_eq!(s, OwnedChars::try_from_utf16(&utf16).unwrap().into_inner());
+    assert_eq!(s, OwnedCharIndices::from_utf16_lossy(&utf16).into_inner());
+}
+
+#[test]
+fn from_utf8_lossy_owned() {
+    // valid input keeps the allocation
+    let bytes = String::from("héllo").into_bytes();
+    let ptr = bytes.as_ptr();
+    let oc = OwnedChars::from_utf8_lossy_owned(bytes);
+    assert_eq!(("héllo", ptr), (oc.get_inner(), oc.get_inner().as_ptr()));
+    assert_eq!("\u{fffd}x", OwnedCharIndices::from_utf8_lossy_owned(b"\xffx".to_vec()).as_str());
+}
+
+#[test]
+fn clone() {
+    let mut oc = String::from("héllo").into_char_indices();
+    oc.nth(1);
+    let mut copy = oc.clone();
+    assert_eq!(Some((3, 'l')), copy.next());
+    assert_eq!(Some((3, 'l')), oc.next());
+}
+
+#[test]
+fn into_parts() {
+    let s = String::from("héllo");
+    let mut oc = s.clone().into_char_indices();
+    oc.nth(1);
+    assert_eq!((s, 3), oc.into_parts());
+}
+
+#[test]
+fn trait_coverage() {
+    use std::iter::{Filter, FusedIterator, Peekable, Rev, Skip, Take, Zip};
+    use std::marker::PhantomData;
+    use std::str::{Bytes, Chars, CharIndices, Lines, MatchIndices, Split, SplitN};
+
+    // whether a type implements a trait: the inherent const is only found if the bound holds,
+    // otherwise the one from the blanket trait impl is used
+    macro_rules! impls {
+        ($ty:ty: $tr:path) => {{
+            struct Probe<T: ?Sized>(PhantomData<T>);
+            #[allow(dead_code)]
+            trait Fallback {
+                const IMPLS: bool = false;
+            }
+            impl<T: ?Sized> Fallback for Probe<T> {}
+            #[allow(dead_code)]
+            impl<T: ?Sized + $tr> Probe<T> {
+                const IMPLS: bool = true;
+            }
+            <Probe<$ty>>::IMPLS
+        }};
+    }
+
+    // each owned iterator implements an iterator trait iff its std counterpart does, or for
+    // iterators without one, iff the trait is listed
+    macro_rules! check {
+        ($owned:ty, $std:ty) => {
+            assert!(impls!($owned: Iterator));
+            check!(@ $owned, $std: DoubleEndedIterator, ExactSizeIterator, FusedIterator);
+        };
+        ($owned:ty: [$($listed:ident),*]) => {
+            assert!(impls!($owned: Iterator));
+            let listed: &[&str] = &[$(stringify!($listed)),*];
+            check!(@list $owned, listed: DoubleEndedIterator, ExactSizeIterator, FusedIterator);
+        };
+        (@ $owned:ty, $std:ty: $($tr:ident),*) => {
+            $(assert_eq!(impls!($std: $tr), impls!($owned: $tr),
+                         "{} vs {}: {}", stringify!($owned), stringify!($std), stringify!($tr));)*
+        };
+        (@list $owned:ty, $listed:ident: $($tr:ident),*) => {
+            $(assert_eq!($listed.contains(&stringify!($tr)), impls!($owned: $tr),
+                         "{}: {}", stringify!($owned), stringify!($tr));)*
+        };
+    }
+
+    check!(OwnedChars, Chars<'static>);
+    check!(OwnedCharIndices, CharIndices<'static>);
+    check!(OwnedCharRanges, CharIndices<'static>);
+    check!(OwnedCodePoints, Chars<'static>);
+    check!(OwnedCodePointIndices, CharIndices<'static>);
+    check!(OwnedBytes, Bytes<'static>);
+    check!(OwnedSplit, Split<'static, &'static str>);
+    check!(OwnedSplitN, SplitN<'static, &'static str>);
+    check!(OwnedLines, Lines<'static>);
+    check!(OwnedFind, MatchIndices<'static, &'static str>);
+    check!(OwnedAsciiChars, Bytes<'static>);
+    check!(OwnedCharBytes, Chars<'static>);
+    check!(OwnedCharsRev, Rev<Chars<'static>>);
+    check!(OwnedZipChars, Zip<Chars<'static>, Chars<'static>>);
+    check!(Owned<String, fn(&str) -> Bytes>, Bytes<'static>);
+    #[cfg(feature = "std")]
+    check!(ReadChars<&'static [u8]>, std::io::Bytes<&'static [u8]>);
+    #[cfg(feature = "std")]
+    check!(OwnedReadLines<&'static [u8]>, std::io::Lines<&'static [u8]>);
+    #[cfg(feature = "decode")]
+    check!(Utf8Chars: [FusedIterator]);
+    #[cfg(feature = "decode")]
+    check!(ChunkedChars: []);
+    #[cfg(feature = "shell")]
+    check!(OwnedShellWords: [FusedIterator]);
+    #[cfg(feature = "tokens")]
+    check!(tokens::Tokens: [FusedIterator]);
+
+    // adapters
+    check!(TakeChars<OwnedChars>, Take<Chars<'static>>);
+    check!(SkipChars<OwnedChars>, Skip<Chars<'static>>);
+    check!(Counted<OwnedChars>, Chars<'static>);
+    check!(OwnedPeekable<OwnedChars>, Peekable<Chars<'static>>);
+    check!(OwnedFilter<OwnedChars, fn(&char) -> bool>, Filter<Chars<'static>, fn(&char) -> bool>);
+    check!(Reclaiming<OwnedChars>, Chars<'static>);
+    check!(LineCounted<OwnedChars>, Chars<'static>);
+    check!(LineCounted<Reclaiming<OwnedChars>>, Chars<'static>);
+    check!(KnownAscii<OwnedChars>, Bytes<'static>);
+    check!(WithPosition<OwnedChars>: [DoubleEndedIterator, FusedIterator]);
+    check!(Intersperse<OwnedChars>: [FusedIterator]);
+    check!(Dedup<OwnedChars, fn(char) -> char, char>: [FusedIterator]);
+
+    // the probe itself can tell both answers apart
+    assert!(!impls!(OwnedSplit: DoubleEndedIterator));
+    assert!(impls!(OwnedBytes: ExactSizeIterator));
+}
+
+#[test]
+fn accessors() {
+    let mut oc = OwnedCharsExt::into_chars(String::from("héllo"));
+    let mut oci = String::from("héllo").into_char_indices();
+    assert_eq!(Some('o'), oc.next_back());
+    assert_eq!(Some((1, 'é')), oci.nth(1));
+    assert_eq!("héll", oc.as_str());
+    assert_eq!("llo", oci.as_str());
+    assert_eq!("héll", oc.get_inner());
+    assert_eq!("héllo", oci.get_inner());
+
+    // after one item, every owned iterator tells what it has not yielded yet and gives its
+    // String back (adapters through the iterator they wrap)
+    let s = "héllo wörld";
+    macro_rules! check {
+        ($iter:expr, $rest:expr) => {
+            check!($iter, $rest, s, |iter| iter.into_inner())
+        };
+        ($iter:expr, $rest:expr, $inner:expr) => {
+            check!($iter, $rest, $inner, |iter| iter.into_inner())
+        };
+        ($iter:expr, $rest:expr, $inner:expr, |$i:ident| $into_inner:expr) => {{
+            let mut $i = $iter;
+            $i.next();
+            assert_eq!($rest, $i.as_str(), "{}", stringify!($iter));
+            assert_eq!($inner, $i.get_inner(), "{}", stringify!($iter));
+            assert_eq!($inner, $into_inner, "{}", stringify!($iter));
+        }};
+    }
+    let chars = || OwnedChars::from_string(s.to_string());
+    check!(chars(), "éllo wörld");
+    check!(OwnedCharIndices::from_string(s.to_string()), "éllo wörld");
+    check!(OwnedCharRanges::from_string(s.to_string()), "éllo wörld");
+    check!(OwnedCodePoints::from_string(s.to_string()), "éllo wörld");
+    check!(OwnedCodePointIndices::from_string(s.to_string()), "éllo wörld");
+    check!(s.into_char_bytes(), "éllo wörld");
+    check!(s.into_chars_rev(), "héllo wörl", "héllo wörl");
+    check!(s.into_lines(), "");
+    check!(s.into_split(" "), "wörld");
+    check!(s.into_splitn(2, "l"), "lo wörld");
+    check!(s.into_find("l"), "lo wörld");
+    check!(OwnedAsciiChars::from_string(String::from("hello")).unwrap(), "ello", "hello");
+    #[cfg(feature = "shell")]
+    check!(s.into_shell_words(), " wörld");
+    #[cfg(feature = "tokens")]
+    check!(tokens::Tokens::new(s.to_string()), " wörld");
+    check!(chars().take_chars(3), "él");
+    check!(chars().skip_chars(3), "o wörld");
+    check!(chars().counted(), "éllo wörld", s, |iter| iter.into_inner().into_inner());
+    check!(chars().peekable_owned(), "éllo wörld", s, |iter| iter.into_inner().into_inner());
+    check!(chars().filter_owned(|&c| c != 'é'), "éllo wörld", s,
+           |iter| iter.into_inner().into_inner());
+    check!(chars().with_position(), "éllo wörld", s, |iter| iter.into_inner().into_inner());
+    check!(chars().dedup_owned(), "éllo wörld", s, |iter| iter.into_inner().into_inner());
+    check!(chars().intersperse_owned("-"), "éllo wörld", s,
+           |iter| iter.into_inner().into_inner());
+    check!(chars().reclaim_after(64), "éllo wörld", s, |iter| iter.into_inner().into_inner());
+    check!(chars().line_counted(), "éllo wörld", s, |iter| iter.into_inner().into_inner());
+    check!(OwnedChars::from_string(String::from("hello")).check_ascii().unwrap(), "ello", "hello",
+           |iter| iter.into_inner().into_inner());
+
+    // iterators whose input may stop in the middle of a char
+    let mut bytes = s.into_owned_bytes();
+    bytes.next();
+    assert_eq!((Ok("éllo wörld"), s), (bytes.as_str(), bytes.get_inner()));
+    bytes.next();
+    assert!(bytes.as_str().is_err());
+    assert_eq!(&s.as_bytes()[2..], bytes.as_bytes());
+    assert_eq!(s, bytes.into_inner());
+    #[cfg(feature = "decode")]
+    {
+        let mut utf8 = Utf8Chars::from_vec(s.as_bytes().to_vec());
+        utf8.next();
+        assert_eq!((s.as_bytes(), &s.as_bytes()[1..]), (utf8.get_inner(), utf8.as_bytes()));
+        assert_eq!(s.as_bytes(), &utf8.into_inner()[..]);
+    }
+
+    // iterators over two Strings
+    let mut zip = OwnedZipChars::new(s.to_string(), String::from("ab"));
+    zip.next();
+    assert_eq!((("éllo wörld", "b"), (s, "ab")), (zip.as_strs(), zip.get_inner()));
+    assert_eq!((s.to_string(), String::from("ab")), zip.into_inner());
+
+    // readers are handed back like `io::BufReader` does (here, fully read into the buffer)
+    #[cfg(feature = "std")]
+    {
+        let mut read = ReadChars::new(s.as_bytes());
+        read.next();
+        assert!(read.get_ref().is_empty());
+        let mut lines = OwnedReadLines::new(s.as_bytes());
+        lines.next();
+        assert!(lines.into_inner().is_empty());
+    }
+}
+
+#[test]
+fn struct_size() {
+    use core::mem::size_of;
+
+    // a String plus the front position, smaller than a String plus `str::Chars`
+    assert_eq!(size_of::<String>() + size_of::<usize>(), size_of::<OwnedChars>());
+    assert!(size_of::<OwnedChars>() < size_of::<String>() + size_of::<::core::str::Chars>());
+    assert_eq!(size_of::<OwnedChars>(), size_of::<OwnedCharIndices>());
+}
+
+#[cfg(feature = "nightly-std")]
+#[test]
+fn nightly_std() {
+    let mut std_chars = String::from("héllo").into_chars();
+    std_chars.next();
+    let mut oc = OwnedChars::from(std_chars);
+    assert_eq!("éllo", oc.as_str());
+    oc.next();
+    oc.next_back();
+    let std_chars = ::alloc::string::IntoChars::from(oc);
+    assert_eq!("ll", std_chars.as_str());
+}
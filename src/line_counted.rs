@@ -0,0 +1,96 @@
+//! Line-counting adapter
+
+use core::ops::Deref;
+
+/// 1-based line and column (counted in chars) of the next char, updated as input is consumed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct LineCounter {
+    line: u32,
+    col: u32,
+}
+
+impl LineCounter {
+    /// Start at line 1, column 1
+    pub(crate) fn new() -> Self {
+        LineCounter { line: 1, col: 1 }
+    }
+
+    /// Returns the line and column of the next char
+    pub(crate) fn get(&self) -> (u32, u32) {
+        (self.line, self.col)
+    }
+
+    /// Move past one consumed char
+    pub(crate) fn push_char(&mut self, c: char) {
+        if c == '\n' {
+            self.line += 1;
+            self.col = 1;
+        } else {
+            self.col += 1;
+        }
+    }
+
+    /// Move past a consumed string
+    #[cfg(feature = "cursor")]
+    pub(crate) fn push_str(&mut self, s: &str) {
+        match s.rfind('\n') {
+            Some(i) => {
+                self.line += s.bytes().filter(|&b| b == b'\n').count() as u32;
+                self.col = s[i + 1..].chars().count() as u32 + 1;
+            }
+            None => self.col += s.chars().count() as u32,
+        }
+    }
+}
+
+/// Iterator adapter that keeps track of the line and column of the next char
+///
+/// Created by the `line_counted` method on the owned iterators. Lines are separated by `\n`, and
+/// columns are counted in chars. The counters are updated as chars are yielded from the front, so
+/// `line_col` takes constant time; chars taken from the back do not affect them.
+///
+/// To bound memory use as well, call `line_counted` on a `Reclaiming` adapter (as in
+/// `reclaim_after(n).line_counted()`): the counters carry on across reclamations. The wrapped
+/// iterator's accessors remain available through `Deref`.
+///
+/// # Example
+///
+/// ```rust
+/// # use owned_chars::OwnedCharsExt;
+/// let mut chars = String::from("ab\ncdé\nf").into_chars().line_counted();
+/// assert_eq!(chars.line_col(), (1, 1));
+/// chars.nth(5);
+/// assert_eq!(chars.line_col(), (2, 4));
+/// chars.next();
+/// assert_eq!(chars.line_col(), (3, 1));
+/// ```
+#[derive(Debug, Clone)]
+pub struct LineCounted<I> {
+    pub(crate) iter: I,
+    pub(crate) lines: LineCounter,
+}
+
+impl<I> LineCounted<I> {
+    /// Wrap an iterator, starting at line 1, column 1
+    pub fn new(iter: I) -> Self {
+        LineCounted { iter, lines: LineCounter::new() }
+    }
+
+    /// Returns the 1-based line and column (counted in chars) of the next char
+    pub fn line_col(&self) -> (u32, u32) {
+        self.lines.get()
+    }
+
+    /// Consume the adapter and return the wrapped iterator
+    pub fn into_inner(self) -> I {
+        self.iter
+    }
+}
+
+impl<I> Deref for LineCounted<I> {
+    type Target = I;
+
+    fn deref(&self) -> &I {
+        &self.iter
+    }
+}
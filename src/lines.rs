@@ -0,0 +1,150 @@
+//! Owned lines iterator
+
+use alloc::borrow::ToOwned;
+use alloc::string::String;
+use core::iter::{Iterator, DoubleEndedIterator, FusedIterator};
+#[cfg(feature = "lending")]
+use lending::LendingIterator;
+
+/// Iterator over the lines of a string (the string is owned by the iterator)
+///
+/// The output is the same as `str::lines`, except that each line is returned as a new String.
+/// Lines are found with `str::find` and `str::rfind` on the contained String, and `count`, `nth`
+/// and `last` skip lines without allocating them.
+///
+/// # Example
+///
+/// ```rust
+/// # use owned_chars::OwnedLines;
+/// let mut lines = OwnedLines::from_string(String::from("one\r\ntwo\n\nthree\n"));
+/// assert_eq!(lines.next(), Some(String::from("one")));
+/// assert_eq!(lines.as_str(), "two\n\nthree\n");
+/// assert_eq!(lines.next_back(), Some(String::from("three")));
+/// assert_eq!(lines.collect::<Vec<_>>(), ["two", ""]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct OwnedLines {
+    s: String,
+    start: usize,
+    end: usize,
+}
+
+impl OwnedLines {
+    /// Create Self from a String, moving the String into Self
+    pub fn from_string(s: String) -> Self {
+        let end = s.len();
+        OwnedLines { s, start: 0, end }
+    }
+
+    /// Consume this struct and return the contained String
+    pub fn into_inner(self) -> String {
+        self.s
+    }
+
+    /// Returns a string slice of contained `String`
+    pub fn get_inner(&self) -> &str {
+        &self.s
+    }
+
+    /// Returns the part of the contained String that has not been yielded yet
+    pub fn as_str(&self) -> &str {
+        &self.s[self.start..self.end]
+    }
+
+    /// Like `next`, but borrows the line from the contained String instead of allocating
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use owned_chars::OwnedLines;
+    /// let mut lines = OwnedLines::from_string(String::from("a\nb"));
+    /// assert_eq!(lines.next_str(), Some("a"));
+    /// assert_eq!(lines.next_str(), Some("b"));
+    /// assert_eq!(lines.next_str(), None);
+    /// ```
+    pub fn next_str(&mut self) -> Option<&str> {
+        if self.start == self.end {
+            return None;
+        }
+        let line_start = self.start;
+        self.start = match self.as_str().find('\n') {
+            Some(i) => self.start + i + 1,
+            None => self.end,
+        };
+        Some(trim_newline(&self.s[line_start..self.start]))
+    }
+
+    /// Like `next_back`, but borrows the line from the contained String instead of allocating
+    pub fn next_back_str(&mut self) -> Option<&str> {
+        if self.start == self.end {
+            return None;
+        }
+        let line_end = self.end;
+        self.end = {
+            let rest = self.as_str();
+            // the last line's own newline (if any) doesn't start a new line
+            let body = rest.strip_suffix('\n').unwrap_or(rest);
+            match body.rfind('\n') {
+                Some(i) => self.start + i + 1,
+                None => self.start,
+            }
+        };
+        Some(trim_newline(&self.s[self.end..line_end]))
+    }
+}
+
+/// Strip the line ending (`\n` or `\r\n`) from a line
+fn trim_newline(line: &str) -> &str {
+    match line.strip_suffix('\n') {
+        Some(line) => line.strip_suffix('\r').unwrap_or(line),
+        None => line,
+    }
+}
+
+impl Iterator for OwnedLines {
+    type Item = String;
+
+    fn next(&mut self) -> Option<String> {
+        self.next_str().map(str::to_owned)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.end - self.start;
+        (if len > 0 { 1 } else { 0 }, Some(len))
+    }
+
+    fn count(self) -> usize {
+        // every line ends with a newline, except maybe the last one
+        let rest = self.as_str();
+        let newlines = rest.bytes().filter(|&b| b == b'\n').count();
+        newlines + !(rest.is_empty() || rest.ends_with('\n')) as usize
+    }
+
+    fn nth(&mut self, n: usize) -> Option<String> {
+        for _ in 0..n {
+            self.next_str()?;
+        }
+        Iterator::next(self)
+    }
+
+    fn last(mut self) -> Option<String> {
+        self.next_back()
+    }
+}
+
+impl DoubleEndedIterator for OwnedLines {
+    fn next_back(&mut self) -> Option<String> {
+        self.next_back_str().map(str::to_owned)
+    }
+}
+
+iter_traits!(OwnedLines, String, [FusedIterator]);
+
+#[cfg(feature = "lending")]
+impl LendingIterator for OwnedLines {
+    type Item<'a> = &'a str;
+
+    fn next(&mut self) -> Option<&str> {
+        self.next_str()
+    }
+}
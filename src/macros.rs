@@ -0,0 +1,303 @@
+//! Public macros
+
+/// Defines an owned version of a borrowing iterator over `&str`
+///
+/// The generated struct owns a String together with an iterator borrowing from it, and has the
+/// same `from_string`, `get_inner` and `into_inner` methods as the owned iterators in this crate
+/// (plus `as_str` if you say how to get it). The unsafe code needed for this lives inside this
+/// crate, and the generated struct has no fields that its module could misuse.
+///
+/// The inner iterator type is written with a lifetime (`for<'a>` below) standing for the borrow of
+/// the String. Its items are passed through `map` to produce the owned iterator's items,
+/// which must not borrow from the String: the macro checks this at compile time.
+///
+/// # Example
+///
+/// ```rust
+/// #[macro_use] extern crate owned_chars;
+///
+/// owned_iter! {
+///     /// Iterator over the whitespace-separated words of a String
+///     #[derive(Debug)]
+///     pub struct OwnedWords(for<'a> std::str::SplitWhitespace<'a>) -> String {
+///         new: |s| s.split_whitespace(),
+///         map: |word| word.to_owned(),
+///         traits: [DoubleEndedIterator, FusedIterator],
+///     }
+/// }
+///
+/// owned_iter! {
+///     /// Iterator over the uppercase versions of the chars of a String
+///     pub struct OwnedUppercase(for<'a> std::iter::FlatMap<std::str::Chars<'a>,
+///                                                          std::char::ToUppercase,
+///                                                          fn(char) -> std::char::ToUppercase>)
+///         -> char
+///     {
+///         new: |s| s.chars().flat_map(char::to_uppercase as fn(char) -> _),
+///         map: |c| c,
+///     }
+/// }
+///
+/// # fn main() {
+/// let mut words = OwnedWords::from_string(String::from("  owned  words "));
+/// assert_eq!(words.next_back(), Some(String::from("words")));
+/// assert_eq!(words.into_inner(), "  owned  words ");
+///
+/// let upper = OwnedUppercase::from_string(String::from("straße"));
+/// assert_eq!(upper.collect::<String>(), "STRASSE");
+/// # }
+/// ```
+///
+/// Items that borrow from the String are rejected:
+///
+/// ```rust,compile_fail
+/// #[macro_use] extern crate owned_chars;
+///
+/// owned_iter! {
+///     pub struct OwnedWords(for<'a> std::str::SplitWhitespace<'a>) -> &'static str {
+///         new: |s| s.split_whitespace(),
+///         map: |word| word,
+///     }
+/// }
+/// # fn main() {}
+/// ```
+///
+/// The String and the iterator borrowing from it can't be reached directly, even from the module
+/// that defines the struct:
+///
+/// ```rust,compile_fail
+/// #[macro_use] extern crate owned_chars;
+///
+/// owned_iter! {
+///     pub struct OwnedWords(for<'a> std::str::SplitWhitespace<'a>) -> String {
+///         new: |s| s.split_whitespace(),
+///         map: |word| word.to_owned(),
+///     }
+/// }
+///
+/// # fn main() {
+/// let mut words = OwnedWords::from_string(String::from("owned words"));
+/// let leaked: &'static str = words.i.next().unwrap();
+/// # }
+/// ```
+///
+/// ```rust,compile_fail
+/// #[macro_use] extern crate owned_chars;
+///
+/// owned_iter! {
+///     pub struct OwnedWords(for<'a> std::str::SplitWhitespace<'a>) -> String {
+///         new: |s| s.split_whitespace(),
+///         map: |word| word.to_owned(),
+///     }
+/// }
+///
+/// # fn main() {
+/// let mut words = OwnedWords::from_string(String::from("owned words"));
+/// words.s.push_str(" and more");
+/// # }
+/// ```
+#[macro_export]
+macro_rules! owned_iter {
+    (
+        $(#[$attr:meta])*
+        $vis:vis struct $name:ident(for<$lt:lifetime> $inner:ty) -> $item:ty {
+            new: |$s:ident| $new:expr,
+            map: |$x:ident| $map:expr,
+            $(as_str: |$i:ident| $as_str:expr,)?
+            $(traits: [$($tr:ident),*],)?
+        }
+    ) => {
+        $(#[$attr])*
+        $vis struct $name {
+            cell: $crate::__private::Cell<$name>,
+        }
+
+        impl<$lt> $crate::__private::OwnedIterFamily<$lt> for $name {
+            type Inner = $inner;
+        }
+
+        // not all of the generated methods are needed by every user
+        #[allow(dead_code)]
+        impl $name {
+            /// Create Self from a String, moving the String into Self
+            pub fn from_string(s: $crate::__private::String) -> Self {
+                fn new<$lt>($s: &$lt str) -> $inner {
+                    $new
+                }
+
+                $name { cell: $crate::__private::Cell::<$name>::new(s, new) }
+            }
+
+            /// Consume this struct and return the contained String
+            pub fn into_inner(self) -> $crate::__private::String {
+                self.cell.into_inner()
+            }
+
+            /// Returns a string slice of contained `String`
+            pub fn get_inner(&self) -> &str {
+                self.cell.get_inner()
+            }
+
+            $(
+                /// Returns the part of the contained String that has not been consumed yet
+                pub fn as_str(&self) -> &str {
+                    fn as_str<$lt>($i: &$lt $inner) -> &$lt str {
+                        $as_str
+                    }
+                    self.cell.as_str(as_str)
+                }
+            )?
+
+            // Generic over the lifetime, so this only compiles if the output type doesn't borrow
+            // from the String.
+            fn map<$lt>($x: <$inner as Iterator>::Item) -> $item {
+                $map
+            }
+        }
+
+        impl Iterator for $name {
+            type Item = $item;
+
+            fn next(&mut self) -> Option<$item> {
+                fn next<$lt>(i: &mut $inner) -> Option<$item> {
+                    i.next().map($name::map)
+                }
+                self.cell.with_mut(next)
+            }
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                fn size_hint<$lt>(i: &$inner) -> (usize, Option<usize>) {
+                    i.size_hint()
+                }
+                self.cell.with(size_hint)
+            }
+        }
+
+        $($($crate::owned_iter!(@ $tr $name, $lt, $inner, $item);)*)?
+    };
+
+    (@ DoubleEndedIterator $name:ident, $lt:lifetime, $inner:ty, $item:ty) => {
+        impl DoubleEndedIterator for $name {
+            fn next_back(&mut self) -> Option<$item> {
+                fn next_back<$lt>(i: &mut $inner) -> Option<$item> {
+                    i.next_back().map($name::map)
+                }
+                self.cell.with_mut(next_back)
+            }
+        }
+    };
+
+    (@ ExactSizeIterator $name:ident, $lt:lifetime, $inner:ty, $item:ty) => {
+        impl ExactSizeIterator for $name {}
+    };
+
+    (@ FusedIterator $name:ident, $lt:lifetime, $inner:ty, $item:ty) => {
+        impl $crate::__private::FusedIterator for $name {}
+    };
+}
+
+/// Implements the extra iterator traits listed for an owned iterator or adapter. Each one must
+/// list exactly the traits that its std counterpart implements (the `trait_coverage` test checks
+/// this).
+///
+/// `DoubleEndedIterator` is generated from the `pop_back` and `make_item` methods of the char
+/// structs (see `impls!`); the other iterators implement it by hand. The listed traits must be in
+/// scope where the macro is used.
+macro_rules! iter_traits {
+    ($t:ty, $item:ty, [$($tr:ident),*]) => {
+        $(iter_traits!(@ $tr $t, $item);)*
+    };
+
+    (@ DoubleEndedIterator $t:ty, $item:ty) => {
+        impl DoubleEndedIterator for $t {
+            fn next_back(&mut self) -> Option<$item> {
+                let c = self.pop_back();
+                let offset = self.byte_offset() + self.rest().len();
+                c.map(|c| Self::make_item(offset, c))
+            }
+        }
+    };
+
+    (@ ExactSizeIterator $t:ty, $item:ty) => {
+        impl ExactSizeIterator for $t {}
+    };
+
+    (@ FusedIterator $t:ty, $item:ty) => {
+        impl FusedIterator for $t {}
+    };
+}
+
+/// Implementation details of `owned_iter!`
+#[doc(hidden)]
+pub mod __private {
+    pub use alloc::string::String;
+    pub use core::iter::FusedIterator;
+    pub use core::str;
+    use core::fmt;
+
+    /// Names the inner iterator type of an `owned_iter!` struct for any lifetime
+    pub trait OwnedIterFamily<'a> {
+        type Inner: Iterator;
+    }
+
+    /// A String together with an iterator borrowing from it, for the struct generated by
+    /// `owned_iter!`
+    ///
+    /// The fields are private to this crate, so the generated code can only reach the iterator
+    /// through functions that work for any lifetime, and the pretend 'static borrow can't escape.
+    pub struct Cell<T: for<'a> OwnedIterFamily<'a>> {
+        // declared first so that it is dropped before the String it borrows from
+        i: <T as OwnedIterFamily<'static>>::Inner,
+        s: String,
+    }
+
+    impl<T: for<'a> OwnedIterFamily<'a>> Cell<T> {
+        pub fn new<F>(s: String, new: F) -> Self
+            where F: for<'a> FnOnce(&'a str) -> <T as OwnedIterFamily<'a>>::Inner
+        {
+            // The iterator borrows the String's heap buffer, which doesn't move when the String
+            // does, and is never modified or freed while the iterator exists (the fields are
+            // private and `i` is dropped first). That makes it safe to pretend the borrow is
+            // 'static, as long as that lifetime never escapes: the iterator is only handed to
+            // functions that are generic over it.
+            let ptr: *const str = &*s;
+            let i = new(unsafe { &*ptr });
+            Cell { i, s }
+        }
+
+        pub fn into_inner(self) -> String {
+            self.s
+        }
+
+        pub fn get_inner(&self) -> &str {
+            &self.s
+        }
+
+        pub fn as_str<F>(&self, f: F) -> &str
+            where F: for<'a> FnOnce(&'a <T as OwnedIterFamily<'a>>::Inner) -> &'a str
+        {
+            // shortening the lifetime is fine, since the String outlives the borrow of self
+            let i = &self.i as *const _ as *const <T as OwnedIterFamily<'_>>::Inner;
+            f(unsafe { &*i })
+        }
+
+        pub fn with<R, F>(&self, f: F) -> R
+            where F: for<'a, 'b> FnOnce(&'b <T as OwnedIterFamily<'a>>::Inner) -> R
+        {
+            f(&self.i)
+        }
+
+        pub fn with_mut<R, F>(&mut self, f: F) -> R
+            where F: for<'a, 'b> FnOnce(&'b mut <T as OwnedIterFamily<'a>>::Inner) -> R
+        {
+            f(&mut self.i)
+        }
+    }
+
+    impl<T: for<'a> OwnedIterFamily<'a>> fmt::Debug for Cell<T>
+        where <T as OwnedIterFamily<'static>>::Inner: fmt::Debug
+    {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            f.debug_struct("Cell").field("i", &self.i).field("s", &self.s).finish()
+        }
+    }
+}
@@ -0,0 +1,146 @@
+//! Owned adapter for any iterator borrowing from a string
+
+use alloc::rc::Rc;
+use alloc::string::String;
+#[cfg(target_has_atomic = "ptr")]
+use alloc::sync::Arc;
+use core::iter::{Iterator, DoubleEndedIterator, ExactSizeIterator, FusedIterator};
+use core::ops::Deref;
+
+/// Owned strings whose contents stay at the same address when the owner is moved
+///
+/// # Safety
+///
+/// Implementors must dereference to the same string (at the same address) for as long as they
+/// exist, no matter how they are moved, and must not allow it to be modified through a shared
+/// reference.
+///
+/// `Box<str>` is not included: moving a `Box` asserts unique access to its contents, which would
+/// invalidate the iterator's borrow. Convert it with `String::from` instead, which doesn't copy.
+pub unsafe trait StableStr: Deref<Target = str> {}
+
+unsafe impl StableStr for String {}
+unsafe impl StableStr for Rc<str> {}
+#[cfg(target_has_atomic = "ptr")]
+unsafe impl StableStr for Arc<str> {}
+
+/// Functions that create an iterator borrowing from a string slice, for any lifetime of the slice
+///
+/// This is implemented for all suitable `FnOnce(&str) -> I`, and only exists to name the iterator
+/// type `I` for each lifetime (see `Owned`).
+pub trait StrIterFn<'a> {
+    /// The iterator returned when the function is given a `&'a str`
+    type Iter: Iterator;
+
+    /// Call the function
+    fn call(self, s: &'a str) -> Self::Iter;
+}
+
+impl<'a, F, I: Iterator> StrIterFn<'a> for F where F: FnOnce(&'a str) -> I {
+    type Iter = I;
+
+    fn call(self, s: &'a str) -> I {
+        self(s)
+    }
+}
+
+/// Iterator that owns a string together with any iterator borrowing from it
+///
+/// The iterator is created by a function that must work for any lifetime, such as a `str` method
+/// or a plain `fn` item. Closures usually don't qualify because the compiler can't infer that
+/// their return type borrows from their argument. The items must not borrow from the string
+/// (otherwise `Owned` is not an iterator).
+///
+/// For iterators used in more than one place, `owned_iter!` defines a named type with fewer
+/// restrictions.
+///
+/// # Example
+///
+/// ```rust
+/// # use owned_chars::Owned;
+/// use std::rc::Rc;
+/// use std::str::Split;
+///
+/// let escaped = Owned::new(String::from("tab\t"), str::escape_default);
+/// assert_eq!(escaped.collect::<String>(), "tab\\t");
+///
+/// fn field_lengths(s: &str) -> std::iter::Map<Split<char>, fn(&str) -> usize> {
+///     s.split(',').map(str::len)
+/// }
+///
+/// let lengths = Owned::new(Rc::<str>::from("one,three,"), field_lengths);
+/// assert_eq!(lengths.collect::<Vec<_>>(), [3, 5, 0]);
+/// ```
+///
+/// Items that borrow from the string are rejected:
+///
+/// ```rust,compile_fail
+/// # use owned_chars::Owned;
+/// let mut words = Owned::new(String::from("owned words"), str::split_whitespace);
+/// words.next();
+/// ```
+pub struct Owned<O, F: for<'a> StrIterFn<'a>> {
+    // declared first so that it is dropped before the string it borrows from
+    i: <F as StrIterFn<'static>>::Iter,
+    owner: O,
+}
+
+impl<O: StableStr, F: for<'a> StrIterFn<'a>> Owned<O, F> {
+    /// Create Self from an owned string and a function creating the iterator, moving the string
+    /// into Self
+    pub fn new(owner: O, f: F) -> Self {
+        // The string doesn't move when the owner does (see `StableStr`), and it is never modified
+        // or freed while the iterator exists (the fields are private and `i` is dropped first).
+        // That makes it safe to pretend the borrow is 'static, as long as that lifetime never
+        // escapes: `f` works for any lifetime so it can't make use of it, and the Iterator impl
+        // only applies if the item type doesn't depend on the lifetime.
+        let ptr: *const str = &*owner;
+        let i = f.call(unsafe { &*ptr });
+        Owned { i, owner }
+    }
+
+    /// Consume this struct and return the contained string
+    pub fn into_inner(self) -> O {
+        self.owner
+    }
+
+    /// Returns a string slice of the contained string
+    pub fn get_inner(&self) -> &str {
+        &self.owner
+    }
+}
+
+impl<O, F, T> Iterator for Owned<O, F>
+    where F: for<'a> StrIterFn<'a>,
+          for<'a> <F as StrIterFn<'a>>::Iter: Iterator<Item = T>
+{
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.i.next()
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.i.size_hint()
+    }
+}
+
+impl<O, F, T> DoubleEndedIterator for Owned<O, F>
+    where F: for<'a> StrIterFn<'a>,
+          for<'a> <F as StrIterFn<'a>>::Iter: DoubleEndedIterator<Item = T>
+{
+    fn next_back(&mut self) -> Option<T> {
+        self.i.next_back()
+    }
+}
+
+impl<O, F, T> ExactSizeIterator for Owned<O, F>
+    where F: for<'a> StrIterFn<'a>,
+          for<'a> <F as StrIterFn<'a>>::Iter: ExactSizeIterator<Item = T>
+{
+}
+
+impl<O, F, T> FusedIterator for Owned<O, F>
+    where F: for<'a> StrIterFn<'a>,
+          for<'a> <F as StrIterFn<'a>>::Iter: FusedIterator<Item = T>
+{
+}
@@ -0,0 +1,83 @@
+//! String wrappers that implement IntoIterator
+
+use alloc::string::String;
+use core::iter::IntoIterator;
+use {OwnedChars, OwnedCharIndices};
+
+/// A String that can be iterated over by value, yielding its chars (with `OwnedChars`)
+///
+/// Coherence rules forbid implementing `IntoIterator` for String in this crate, so this wrapper
+/// does it instead. See `OwnedStrIndices` for chars with their indices.
+///
+/// # Example
+///
+/// ```rust
+/// # use owned_chars::OwnedStr;
+/// fn count_vowels<I: IntoIterator<Item = char>>(chars: I) -> usize {
+///     chars.into_iter().filter(|c| "aeiou".contains(*c)).count()
+/// }
+///
+/// let s = OwnedStr(String::from("education"));
+/// assert_eq!(count_vowels(s), 5);
+///
+/// for c in OwnedStr::from(String::from("é")) {
+///     assert_eq!(c, 'é');
+/// }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct OwnedStr(pub String);
+
+/// A String that can be iterated over by value, yielding its chars and their indices (with
+/// `OwnedCharIndices`)
+///
+/// # Example
+///
+/// ```rust
+/// # use owned_chars::OwnedStrIndices;
+/// let s = OwnedStrIndices(String::from("hé!"));
+/// assert_eq!(s.into_iter().collect::<Vec<_>>(), [(0, 'h'), (1, 'é'), (3, '!')]);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct OwnedStrIndices(pub String);
+
+impl IntoIterator for OwnedStr {
+    type Item = char;
+    type IntoIter = OwnedChars;
+
+    fn into_iter(self) -> OwnedChars {
+        OwnedChars::from_string(self.0)
+    }
+}
+
+impl IntoIterator for OwnedStrIndices {
+    type Item = (usize, char);
+    type IntoIter = OwnedCharIndices;
+
+    fn into_iter(self) -> OwnedCharIndices {
+        OwnedCharIndices::from_string(self.0)
+    }
+}
+
+impl From<String> for OwnedStr {
+    fn from(s: String) -> Self {
+        OwnedStr(s)
+    }
+}
+
+impl From<String> for OwnedStrIndices {
+    fn from(s: String) -> Self {
+        OwnedStrIndices(s)
+    }
+}
+
+impl From<OwnedStr> for String {
+    fn from(s: OwnedStr) -> Self {
+        s.0
+    }
+}
+
+impl From<OwnedStrIndices> for String {
+    fn from(s: OwnedStrIndices) -> Self {
+        s.0
+    }
+}
@@ -0,0 +1,77 @@
+//! Peekable adapter
+
+use core::iter::{Iterator, DoubleEndedIterator, FusedIterator};
+use core::ops::Deref;
+
+/// Iterator adapter with a `peek` method that returns a reference, like `Peekable`
+///
+/// Created by the `peekable_owned` method on the owned iterators. Unlike `Iterator::peekable`, the
+/// wrapped iterator's accessors remain available through `Deref`. Peeking doesn't move the wrapped
+/// iterator, so `as_str` still includes the peeked char.
+///
+/// # Example
+///
+/// ```rust
+/// # use owned_chars::OwnedCharsExt;
+/// let mut chars = String::from("ab").into_chars().peekable_owned();
+/// assert_eq!(chars.peek(), Some(&'a'));
+/// assert_eq!(chars.as_str(), "ab");
+/// assert_eq!(chars.next(), Some('a'));
+/// assert_eq!(chars.as_str(), "b");
+/// assert_eq!(chars.into_inner().into_inner(), "ab");
+/// ```
+#[derive(Debug, Clone)]
+pub struct OwnedPeekable<I: Iterator> {
+    iter: I,
+    /// the item that `next` will return, if it has been peeked
+    peeked: Option<Option<I::Item>>,
+}
+
+impl<I: Iterator> OwnedPeekable<I> {
+    /// Wrap an iterator
+    pub fn new(iter: I) -> Self {
+        OwnedPeekable { iter, peeked: None }
+    }
+
+    /// Consume the adapter and return the wrapped iterator (which still has the peeked item)
+    pub fn into_inner(self) -> I {
+        self.iter
+    }
+
+    /// Returns a reference to the item that `next` will return, given the item of the wrapped
+    /// iterator (which must not have moved since)
+    pub(crate) fn peek_with<F: FnOnce(&I) -> Option<I::Item>>(&mut self, peek: F) -> Option<&I::Item> {
+        let iter = &self.iter;
+        self.peeked.get_or_insert_with(|| peek(iter)).as_ref()
+    }
+}
+
+impl<I: Iterator> Deref for OwnedPeekable<I> {
+    type Target = I;
+
+    fn deref(&self) -> &I {
+        &self.iter
+    }
+}
+
+impl<I: Iterator> Iterator for OwnedPeekable<I> {
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<I::Item> {
+        self.peeked = None;
+        self.iter.next()
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<I: DoubleEndedIterator> DoubleEndedIterator for OwnedPeekable<I> {
+    fn next_back(&mut self) -> Option<I::Item> {
+        // this may take the peeked item
+        self.peeked = None;
+        self.iter.next_back()
+    }
+}
+
+impl<I: FusedIterator> FusedIterator for OwnedPeekable<I> {}
@@ -0,0 +1,75 @@
+//! Position-tagging adapter
+
+use core::ops::Deref;
+
+/// Where an item is in the sequence yielded by `WithPosition`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Position {
+    /// The first of several items
+    First,
+    /// Neither the first nor the last item
+    Middle,
+    /// The last of several items
+    Last,
+    /// The only item
+    Only,
+}
+
+impl Position {
+    pub(crate) fn new(first: bool, last: bool) -> Self {
+        match (first, last) {
+            (true, true) => Position::Only,
+            (true, false) => Position::First,
+            (false, true) => Position::Last,
+            (false, false) => Position::Middle,
+        }
+    }
+}
+
+/// Iterator adapter that tags each item with its `Position`
+///
+/// Created by the `with_position` method on the owned iterators. Since the wrapped iterator knows
+/// how much input is left, no lookahead is needed, and its accessors remain available through
+/// `Deref`. Items taken from the back are tagged too.
+///
+/// # Example
+///
+/// ```rust
+/// # use owned_chars::{OwnedCharsExt, Position};
+/// let mut chars = String::from("abc").into_chars().with_position();
+/// assert_eq!(chars.next(), Some((Position::First, 'a')));
+/// assert_eq!(chars.as_str(), "bc");
+/// assert_eq!(chars.next(), Some((Position::Middle, 'b')));
+/// assert_eq!(chars.next(), Some((Position::Last, 'c')));
+///
+/// let mut chars = String::from("x").into_chars().with_position();
+/// assert_eq!(chars.next(), Some((Position::Only, 'x')));
+/// ```
+#[derive(Debug, Clone)]
+pub struct WithPosition<I> {
+    pub(crate) iter: I,
+    /// whether an item has been yielded from the front
+    pub(crate) front: bool,
+    /// whether an item has been yielded from the back
+    pub(crate) back: bool,
+}
+
+impl<I> WithPosition<I> {
+    /// Wrap an iterator
+    pub fn new(iter: I) -> Self {
+        WithPosition { iter, front: false, back: false }
+    }
+
+    /// Consume the adapter and return the wrapped iterator
+    pub fn into_inner(self) -> I {
+        self.iter
+    }
+}
+
+impl<I> Deref for WithPosition<I> {
+    type Target = I;
+
+    fn deref(&self) -> &I {
+        &self.iter
+    }
+}
@@ -0,0 +1,30 @@
+//! Convenience re-exports
+//!
+//! This brings the extension trait and the owned iterator types into scope with one import.
+//! `lending::LendingIterator` is left out on purpose: its `next` would be ambiguous with
+//! `Iterator::next` on the iterators that implement both.
+//!
+//! # Example
+//!
+//! ```rust
+//! use owned_chars::prelude::*;
+//!
+//! fn first_word(s: String) -> Option<String> {
+//!     let mut chars: OwnedChars = s.into_chars();
+//!     chars.next_word().map(String::from)
+//! }
+//!
+//! assert_eq!(first_word(String::from("  hello world")).as_deref(), Some("hello"));
+//! ```
+
+pub use {OwnedCharsExt, OwnedChars, OwnedCharIndices, OwnedCharRanges, OwnedBytes, OwnedAsciiChars,
+         OwnedLines, OwnedSplit, OwnedSplitN, OwnedFind, OwnedZipChars, OwnedStr, OwnedStrIndices,
+         Counted, TakeChars, SkipChars, OwnedPeekable, OwnedFilter, Position, WithPosition,
+         Intersperse, Dedup, Reclaiming, LineCounted, KnownAscii, OwnedCodePoints,
+         OwnedCodePointIndices, OwnedCharBytes, OwnedCharsRev};
+#[cfg(feature = "shell")]
+pub use OwnedShellWords;
+#[cfg(feature = "decode")]
+pub use {Utf8Chars, ChunkedChars};
+#[cfg(feature = "std")]
+pub use {ReadChars, OwnedReadLines};
@@ -0,0 +1,255 @@
+//! Iterators over a reader
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::iter::Iterator;
+use core::str;
+use std::io::{self, BufRead, Read};
+#[cfg(feature = "lending")]
+use lending::LendingIterator;
+
+const BUF_SIZE: usize = 8 * 1024;
+
+/// Iterator over the chars of UTF-8 text read from any `io::Read` (the reader is owned by the
+/// iterator)
+///
+/// The input is read in chunks and decoded incrementally, so it never has to be held in memory all
+/// at once. Reading errors are passed through (except `Interrupted`, which is retried). Invalid
+/// UTF-8 is reported as an `InvalidData` error, or replaced by U+FFFD REPLACEMENT CHARACTER if the
+/// iterator was created with `lossy`. Either way, iteration can continue after an error.
+///
+/// # Example
+///
+/// ```rust
+/// # use owned_chars::ReadChars;
+/// let input: &[u8] = b"h\xc3\xa9llo";
+/// let chars = ReadChars::new(input).collect::<Result<String, _>>().unwrap();
+/// assert_eq!(chars, "héllo");
+///
+/// let input: &[u8] = b"a\xffb";
+/// assert!(ReadChars::new(input).nth(1).unwrap().is_err());
+/// assert_eq!(ReadChars::lossy(input).collect::<Result<String, _>>().unwrap(), "a\u{fffd}b");
+/// ```
+#[derive(Debug)]
+pub struct ReadChars<R> {
+    reader: R,
+    buf: Vec<u8>,
+    pos: usize,
+    lossy: bool,
+}
+
+impl<R: Read> ReadChars<R> {
+    /// Create Self from a reader, reporting invalid UTF-8 as errors
+    pub fn new(reader: R) -> Self {
+        ReadChars { reader, buf: Vec::with_capacity(BUF_SIZE), pos: 0, lossy: false }
+    }
+
+    /// Create Self from a reader, replacing invalid UTF-8 with U+FFFD REPLACEMENT CHARACTER
+    /// (following the same rules as `String::from_utf8_lossy`)
+    pub fn lossy(reader: R) -> Self {
+        ReadChars { lossy: true, ..ReadChars::new(reader) }
+    }
+
+    /// Borrow the contained reader
+    pub fn get_ref(&self) -> &R {
+        &self.reader
+    }
+
+    /// Mutably borrow the contained reader
+    ///
+    /// Reading from it directly will skip over data that has already been buffered.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.reader
+    }
+
+    /// Consume this struct and return the contained reader (any buffered input is lost)
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+
+    /// Make sure at least `n` bytes are buffered, returning false if the reader ran out first
+    fn fill(&mut self, n: usize) -> io::Result<bool> {
+        while self.buf.len() - self.pos < n {
+            if self.pos > 0 {
+                // move the unread bytes to the front to make room
+                let len = self.buf.len() - self.pos;
+                self.buf.copy_within(self.pos.., 0);
+                self.buf.truncate(len);
+                self.pos = 0;
+            }
+
+            let len = self.buf.len();
+            self.buf.resize(BUF_SIZE, 0);
+            let result = self.reader.read(&mut self.buf[len..]);
+            self.buf.truncate(len + result.as_ref().map_or(0, |&n| n));
+            match result {
+                Ok(0) => return Ok(false),
+                Ok(_) => {}
+                Err(ref e) if e.kind() == io::ErrorKind::Interrupted => {}
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(true)
+    }
+
+    /// Skip `len` invalid bytes and report them
+    fn invalid(&mut self, len: usize) -> io::Result<char> {
+        self.pos += len;
+        if self.lossy {
+            Ok('\u{fffd}')
+        } else {
+            Err(io::Error::new(io::ErrorKind::InvalidData, "stream did not contain valid UTF-8"))
+        }
+    }
+}
+
+/// Returns the length of the UTF-8 sequence started by a byte (1 for invalid lead bytes, which will
+/// be rejected by the decoder anyway)
+fn utf8_width(b: u8) -> usize {
+    match b {
+        0xc0..=0xdf => 2,
+        0xe0..=0xef => 3,
+        0xf0..=0xff => 4,
+        _ => 1,
+    }
+}
+
+impl<R: Read> Iterator for ReadChars<R> {
+    type Item = io::Result<char>;
+
+    fn next(&mut self) -> Option<io::Result<char>> {
+        match self.fill(1) {
+            Ok(true) => {}
+            Ok(false) => return None,
+            Err(e) => return Some(Err(e)),
+        }
+        let width = utf8_width(self.buf[self.pos]);
+        let complete = match self.fill(width) {
+            Ok(complete) => complete,
+            Err(e) => return Some(Err(e)),
+        };
+
+        let end = if complete { self.pos + width } else { self.buf.len() };
+        Some(match str::from_utf8(&self.buf[self.pos..end]) {
+            Ok(s) => {
+                self.pos = end;
+                Ok(s.chars().next().unwrap())
+            }
+            // error_len is None for a sequence cut short by the end of the input
+            Err(e) => {
+                let len = e.error_len().unwrap_or(end - self.pos);
+                self.invalid(len)
+            }
+        })
+    }
+}
+
+/// Iterator over the lines of UTF-8 text read from any `io::BufRead` (the reader is owned by the
+/// iterator)
+///
+/// The output is the same as `BufRead::lines`, but the reader is owned, so a function can open a
+/// file and return the lines. `next_str` (and the `LendingIterator` impl) reads each line into one
+/// reused buffer instead of allocating a new String per line.
+///
+/// Invalid UTF-8 is reported as an `InvalidData` error, and iteration can continue after it with
+/// the next line. Reading errors are passed through.
+///
+/// # Example
+///
+/// ```rust
+/// # use owned_chars::OwnedReadLines;
+/// let input: &[u8] = b"one\r\ntwo\n\nthree";
+/// let mut lines = OwnedReadLines::new(input);
+/// assert_eq!(lines.next().unwrap().unwrap(), "one");
+/// assert_eq!(lines.next_str().unwrap().unwrap(), "two");
+/// assert_eq!(lines.collect::<Result<Vec<_>, _>>().unwrap(), ["", "three"]);
+/// ```
+#[derive(Debug)]
+pub struct OwnedReadLines<R> {
+    reader: R,
+    /// buffer reused by `next_str`
+    buf: String,
+}
+
+impl<R: BufRead> OwnedReadLines<R> {
+    /// Create Self from a reader, moving the reader into Self
+    pub fn new(reader: R) -> Self {
+        OwnedReadLines { reader, buf: String::new() }
+    }
+
+    /// Borrow the contained reader
+    pub fn get_ref(&self) -> &R {
+        &self.reader
+    }
+
+    /// Mutably borrow the contained reader
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.reader
+    }
+
+    /// Consume this struct and return the contained reader
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+
+    /// Like `next`, but reads the line into a buffer owned by the iterator and borrows it, instead
+    /// of allocating a new String
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use owned_chars::OwnedReadLines;
+    /// let mut lines = OwnedReadLines::new(&b"a\nbc\n"[..]);
+    /// let mut total = 0;
+    /// while let Some(line) = lines.next_str() {
+    ///     total += line.unwrap().len();
+    /// }
+    /// assert_eq!(total, 3);
+    /// ```
+    pub fn next_str(&mut self) -> Option<io::Result<&str>> {
+        let mut buf = core::mem::take(&mut self.buf);
+        buf.clear();
+        let result = read_line(&mut self.reader, &mut buf);
+        self.buf = buf;
+        match result {
+            Some(Ok(())) => Some(Ok(&self.buf)),
+            Some(Err(e)) => Some(Err(e)),
+            None => None,
+        }
+    }
+}
+
+/// Read one line into `buf` without its line ending, or return None at the end of the input
+fn read_line<R: BufRead>(reader: &mut R, buf: &mut String) -> Option<io::Result<()>> {
+    match reader.read_line(buf) {
+        Ok(0) => None,
+        Ok(_) => {
+            if buf.ends_with('\n') {
+                buf.pop();
+                if buf.ends_with('\r') {
+                    buf.pop();
+                }
+            }
+            Some(Ok(()))
+        }
+        Err(e) => Some(Err(e)),
+    }
+}
+
+impl<R: BufRead> Iterator for OwnedReadLines<R> {
+    type Item = io::Result<String>;
+
+    fn next(&mut self) -> Option<io::Result<String>> {
+        let mut line = String::new();
+        read_line(&mut self.reader, &mut line).map(|result| result.map(|()| line))
+    }
+}
+
+#[cfg(feature = "lending")]
+impl<R: BufRead> LendingIterator for OwnedReadLines<R> {
+    type Item<'a> = io::Result<&'a str> where R: 'a;
+
+    fn next(&mut self) -> Option<io::Result<&str>> {
+        self.next_str()
+    }
+}
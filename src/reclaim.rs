@@ -0,0 +1,58 @@
+//! Memory-reclaiming adapter
+
+use core::ops::Deref;
+
+/// Iterator adapter that drops the consumed prefix of the contained String whenever it reaches a
+/// threshold, so that memory use stays bounded while iterating over a long String
+///
+/// Created by the `reclaim_after` method on the owned iterators. Before yielding a char, `next`
+/// calls `truncate_consumed` on the wrapped iterator if at least `threshold` bytes have been
+/// consumed. Each reclamation shifts the rest of the String down, so a larger threshold means
+/// fewer (but bigger) moves.
+///
+/// As with `truncate_consumed`, byte positions are relative to the String left by the last
+/// reclamation; add `reclaimed_len` to get positions in the original String. The wrapped
+/// iterator's accessors remain available through `Deref`.
+///
+/// # Example
+///
+/// ```rust
+/// # use owned_chars::OwnedCharsExt;
+/// let mut chars = String::from("abcdefgh").into_char_indices().reclaim_after(4);
+/// assert_eq!(chars.nth(4), Some((0, 'e')));
+/// assert_eq!(chars.get_inner(), "efgh");
+/// assert_eq!(chars.reclaimed_len(), 4);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Reclaiming<I> {
+    pub(crate) iter: I,
+    /// consumed length at which `next` drops the consumed prefix
+    pub(crate) threshold: usize,
+    /// number of bytes dropped from the front of the String
+    pub(crate) reclaimed: usize,
+}
+
+impl<I> Reclaiming<I> {
+    /// Wrap an iterator, dropping the consumed prefix whenever it reaches `threshold` bytes
+    pub fn new(iter: I, threshold: usize) -> Self {
+        Reclaiming { iter, threshold, reclaimed: 0 }
+    }
+
+    /// Returns the total number of bytes dropped from the front of the contained String
+    pub fn reclaimed_len(&self) -> usize {
+        self.reclaimed
+    }
+
+    /// Consume the adapter and return the wrapped iterator
+    pub fn into_inner(self) -> I {
+        self.iter
+    }
+}
+
+impl<I> Deref for Reclaiming<I> {
+    type Target = I;
+
+    fn deref(&self) -> &I {
+        &self.iter
+    }
+}
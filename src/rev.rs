@@ -0,0 +1,103 @@
+//! Reversed owned chars iterator
+
+use alloc::string::String;
+use core::iter::{Iterator, DoubleEndedIterator, FusedIterator};
+use OwnedChars;
+
+/// Iterator over the chars of a string from back to front (the string is owned by the iterator)
+///
+/// Created by `into_chars_rev` or `OwnedChars::into_rev`. Unlike `Rev<OwnedChars>`, the accessors
+/// stay available: `as_str` returns the prefix that has not been yielded yet, and `into_forward`
+/// turns it back into an `OwnedChars` over that prefix.
+///
+/// # Example
+///
+/// ```rust
+/// # use owned_chars::OwnedCharsExt;
+/// let mut chars = String::from("héllo").into_chars_rev();
+/// assert_eq!(chars.next(), Some('o'));
+/// assert_eq!(chars.next(), Some('l'));
+/// assert_eq!(chars.as_str(), "hél");
+/// assert_eq!(chars.byte_offset(), 4);
+///
+/// let mut forward = chars.into_forward();
+/// assert_eq!(forward.next(), Some('h'));
+/// assert_eq!(forward.as_str(), "él");
+/// ```
+#[derive(Debug, Clone)]
+pub struct OwnedCharsRev {
+    iter: OwnedChars,
+}
+
+impl OwnedCharsRev {
+    /// Create Self from a String, moving the String into Self
+    pub fn from_string(s: String) -> Self {
+        OwnedChars::from_string(s).into_rev()
+    }
+
+    /// Consume this struct and return the contained String
+    pub fn into_inner(self) -> String {
+        self.iter.into_inner()
+    }
+
+    /// Returns a string slice of contained `String`
+    pub fn get_inner(&self) -> &str {
+        self.iter.get_inner()
+    }
+
+    /// Returns the part of the contained String that has not been yielded yet (a prefix of it)
+    pub fn as_str(&self) -> &str {
+        self.iter.as_str()
+    }
+
+    /// Returns the byte position just after the next char, i.e. the end of `as_str`
+    pub fn byte_offset(&self) -> usize {
+        self.iter.byte_offset() + self.iter.as_str().len()
+    }
+
+    /// Returns true if there are no chars left
+    pub fn is_empty(&self) -> bool {
+        self.iter.is_empty()
+    }
+
+    /// Turn this back into a forward iterator over the chars that have not been yielded yet
+    pub fn into_forward(self) -> OwnedChars {
+        self.iter
+    }
+}
+
+impl OwnedChars {
+    /// Turn this into an iterator that yields the remaining chars from back to front, keeping the
+    /// accessors available (see `OwnedCharsRev`)
+    pub fn into_rev(self) -> OwnedCharsRev {
+        OwnedCharsRev { iter: self }
+    }
+}
+
+impl Iterator for OwnedCharsRev {
+    type Item = char;
+
+    fn next(&mut self) -> Option<char> {
+        self.iter.next_back()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+
+    fn count(self) -> usize {
+        self.iter.count()
+    }
+
+    fn last(mut self) -> Option<char> {
+        self.iter.next()
+    }
+}
+
+impl DoubleEndedIterator for OwnedCharsRev {
+    fn next_back(&mut self) -> Option<char> {
+        self.iter.next()
+    }
+}
+
+iter_traits!(OwnedCharsRev, char, [FusedIterator]);
@@ -0,0 +1,166 @@
+//! Owned shell-word splitting
+
+use alloc::borrow::Cow;
+use alloc::string::String;
+use core::iter::{Iterator, FusedIterator};
+use error::UnterminatedQuote;
+
+/// Iterator over the words of a shell-style command line (the string is owned by the iterator)
+///
+/// Words are separated by whitespace. As in a POSIX shell, single quotes preserve everything up to
+/// the closing quote, double quotes preserve everything except backslash escapes of `$`, `` ` ``,
+/// `"`, `\` and newline, a backslash outside quotes escapes the next char, a backslash-newline
+/// pair is removed, and a `#` at the start of a word begins a comment that lasts until the end of
+/// the line. No other expansion is done.
+///
+/// An unterminated quote or a trailing backslash is reported as an error, after which the iterator
+/// is exhausted.
+///
+/// # Example
+///
+/// ```rust
+/// # use owned_chars::OwnedShellWords;
+/// let line = String::from(r#"grep -e 'a b' "$HOME"/x\ y # search"#);
+/// let words = OwnedShellWords::from_string(line).collect::<Result<Vec<_>, _>>().unwrap();
+/// assert_eq!(words, ["grep", "-e", "a b", "$HOME/x y"]);
+///
+/// let mut words = OwnedShellWords::from_string(String::from("echo 'oops"));
+/// assert_eq!(words.next(), Some(Ok(String::from("echo"))));
+/// assert_eq!(words.next().unwrap().unwrap_err().offset(), 5);
+/// assert_eq!(words.next(), None);
+/// ```
+#[derive(Debug, Clone)]
+pub struct OwnedShellWords {
+    s: String,
+    pos: usize,
+}
+
+impl OwnedShellWords {
+    /// Create Self from a String, moving the String into Self
+    pub fn from_string(s: String) -> Self {
+        OwnedShellWords { s, pos: 0 }
+    }
+
+    /// Consume this struct and return the contained String
+    pub fn into_inner(self) -> String {
+        self.s
+    }
+
+    /// Returns a string slice of contained `String`
+    pub fn get_inner(&self) -> &str {
+        &self.s
+    }
+
+    /// Returns the part of the contained String that has not been split yet
+    pub fn as_str(&self) -> &str {
+        &self.s[self.pos..]
+    }
+
+    /// Like `next`, but borrows the word from the contained String if it contains no quotes or
+    /// escapes
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use owned_chars::OwnedShellWords;
+    /// use std::borrow::Cow;
+    ///
+    /// let mut words = OwnedShellWords::from_string(String::from("ls 'My Documents'"));
+    /// assert!(matches!(words.next_word(), Some(Ok(Cow::Borrowed("ls")))));
+    /// assert!(matches!(words.next_word(), Some(Ok(Cow::Owned(_)))));
+    /// ```
+    pub fn next_word(&mut self) -> Option<Result<Cow<'_, str>, UnterminatedQuote>> {
+        // skip whitespace and comments
+        loop {
+            let rest = &self.s[self.pos..];
+            let trimmed = rest.trim_start();
+            self.pos += rest.len() - trimmed.len();
+            if !trimmed.starts_with('#') {
+                break;
+            }
+            self.pos += trimmed.find('\n').unwrap_or(trimmed.len());
+        }
+        if self.pos == self.s.len() {
+            return None;
+        }
+
+        let start = self.pos;
+        match parse_word(&self.s[start..]) {
+            Ok((word, len)) => {
+                self.pos += len;
+                Some(Ok(word.map_or(Cow::Borrowed(&self.s[start..start + len]), Cow::Owned)))
+            }
+            Err(offset) => {
+                self.pos = self.s.len();
+                Some(Err(UnterminatedQuote::new(start + offset)))
+            }
+        }
+    }
+}
+
+/// Parse the word at the start of `s`, returning its unquoted text (or None if it is the same as
+/// the input) and the number of bytes it takes up, or the relative position of an unterminated
+/// quote or escape
+fn parse_word(s: &str) -> Result<(Option<String>, usize), usize> {
+    let mut word = String::new();
+    let mut plain = true;
+    let mut chars = s.char_indices();
+    while let Some((i, c)) = chars.next() {
+        if plain && (c == '\'' || c == '"' || c == '\\') {
+            // from here on the word differs from the input, so it has to be built up
+            word.push_str(&s[..i]);
+            plain = false;
+        }
+        match c {
+            c if c.is_whitespace() => return Ok((if plain { None } else { Some(word) }, i)),
+            '\'' => {
+                loop {
+                    match chars.next() {
+                        Some((_, '\'')) => break,
+                        Some((_, c)) => word.push(c),
+                        None => return Err(i),
+                    }
+                }
+            }
+            '"' => {
+                loop {
+                    match chars.next() {
+                        Some((_, '"')) => break,
+                        Some((_, '\\')) => match chars.next() {
+                            Some((_, '\n')) => {}
+                            Some((_, c @ '$')) | Some((_, c @ '`')) | Some((_, c @ '"'))
+                                | Some((_, c @ '\\')) => word.push(c),
+                            Some((_, c)) => {
+                                word.push('\\');
+                                word.push(c);
+                            }
+                            None => return Err(i),
+                        },
+                        Some((_, c)) => word.push(c),
+                        None => return Err(i),
+                    }
+                }
+            }
+            '\\' => {
+                match chars.next() {
+                    Some((_, '\n')) => {}
+                    Some((_, c)) => word.push(c),
+                    None => return Err(i),
+                }
+            }
+            c if !plain => word.push(c),
+            _ => {}
+        }
+    }
+    Ok((if plain { None } else { Some(word) }, s.len()))
+}
+
+impl Iterator for OwnedShellWords {
+    type Item = Result<String, UnterminatedQuote>;
+
+    fn next(&mut self) -> Option<Result<String, UnterminatedQuote>> {
+        self.next_word().map(|word| word.map(Cow::into_owned))
+    }
+}
+
+iter_traits!(OwnedShellWords, Result<String, UnterminatedQuote>, [FusedIterator]);
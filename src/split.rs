@@ -0,0 +1,251 @@
+//! Owned split iterators
+
+use alloc::borrow::ToOwned;
+use alloc::string::String;
+use core::iter::{Iterator, FusedIterator};
+use find::Searcher;
+#[cfg(feature = "lending")]
+use lending::LendingIterator;
+
+/// Iterator over the substrings of a string separated by a pattern (the string and the pattern are
+/// owned by the iterator)
+///
+/// The output is the same as `str::split`, except that each piece is returned as a new String.
+///
+/// # Example
+///
+/// ```rust
+/// # use owned_chars::OwnedSplit;
+/// let mut fields = OwnedSplit::from_string(String::from("a,b,,c"), ",");
+/// assert_eq!(fields.next(), Some(String::from("a")));
+/// assert_eq!(fields.remainder(), Some("b,,c"));
+/// assert_eq!(fields.collect::<Vec<_>>(), ["b", "", "c"]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct OwnedSplit {
+    s: String,
+    searcher: Searcher,
+    /// start of the next piece
+    start: usize,
+    finished: bool,
+}
+
+impl OwnedSplit {
+    /// Create Self from a String, moving the String into Self
+    pub fn from_string(s: String, pat: &str) -> Self {
+        OwnedSplit { s, searcher: Searcher::new(pat), start: 0, finished: false }
+    }
+
+    /// Don't split at occurrences of the pattern between a pair of `quote` chars, or right after
+    /// `escape` (which also escapes quotes)
+    ///
+    /// The quotes and escapes are kept in the pieces. This covers simple CSV-like formats.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use owned_chars::OwnedSplit;
+    /// let line = String::from(r#"1,"Smith, J.",a\,b"#);
+    /// let fields = OwnedSplit::from_string(line, ",").quoted('"', Some('\\'));
+    /// assert_eq!(fields.collect::<Vec<_>>(), ["1", r#""Smith, J.""#, r"a\,b"]);
+    /// ```
+    pub fn quoted(mut self, quote: char, escape: Option<char>) -> Self {
+        self.searcher.set_quoting(quote, escape);
+        self
+    }
+
+    /// Consume this struct and return the contained String
+    pub fn into_inner(self) -> String {
+        self.s
+    }
+
+    /// Returns a string slice of contained `String`
+    pub fn get_inner(&self) -> &str {
+        &self.s
+    }
+
+    /// Returns the part of the contained String that has not been split yet (empty once the
+    /// iterator is exhausted)
+    pub fn as_str(&self) -> &str {
+        self.remainder().unwrap_or("")
+    }
+
+    /// Returns the part of the contained String that has not been split yet, or None if the
+    /// iterator is exhausted (like std's unstable `Split::remainder`)
+    pub fn remainder(&self) -> Option<&str> {
+        if self.finished {
+            None
+        } else {
+            Some(&self.s[self.start..])
+        }
+    }
+
+    /// Consume this struct and return the part of the contained String that has not been split
+    /// yet, or None if the iterator is exhausted
+    ///
+    /// The existing allocation is reused.
+    pub fn into_remainder(self) -> Option<String> {
+        if self.finished {
+            None
+        } else {
+            let mut s = self.s;
+            s.drain(..self.start);
+            Some(s)
+        }
+    }
+
+    /// Like `next`, but borrows the piece from the contained String instead of allocating
+    pub fn next_str(&mut self) -> Option<&str> {
+        if self.finished {
+            return None;
+        }
+        let start = self.start;
+        match self.searcher.next_match(&self.s) {
+            Some((a, b)) => {
+                self.start = b;
+                Some(&self.s[start..a])
+            }
+            None => {
+                self.finished = true;
+                Some(&self.s[start..])
+            }
+        }
+    }
+}
+
+impl Iterator for OwnedSplit {
+    type Item = String;
+
+    fn next(&mut self) -> Option<String> {
+        self.next_str().map(str::to_owned)
+    }
+}
+
+iter_traits!(OwnedSplit, String, [FusedIterator]);
+
+/// Iterator over at most `n` substrings of a string separated by a pattern (the string and the
+/// pattern are owned by the iterator)
+///
+/// The output is the same as `str::splitn`, except that each piece is returned as a new String.
+///
+/// # Example
+///
+/// ```rust
+/// # use owned_chars::OwnedSplitN;
+/// let mut parts = OwnedSplitN::from_string(String::from("key=a=b"), 2, "=");
+/// assert_eq!(parts.next(), Some(String::from("key")));
+/// assert_eq!(parts.into_remainder(), Some(String::from("a=b")));
+/// ```
+#[derive(Debug, Clone)]
+pub struct OwnedSplitN {
+    iter: OwnedSplit,
+    count: usize,
+}
+
+impl OwnedSplitN {
+    /// Create Self from a String, moving the String into Self
+    pub fn from_string(s: String, n: usize, pat: &str) -> Self {
+        OwnedSplitN { iter: OwnedSplit::from_string(s, pat), count: n }
+    }
+
+    /// Don't split at occurrences of the pattern between a pair of `quote` chars, or right after
+    /// `escape` (see `OwnedSplit::quoted`)
+    pub fn quoted(mut self, quote: char, escape: Option<char>) -> Self {
+        self.iter = self.iter.quoted(quote, escape);
+        self
+    }
+
+    /// Consume this struct and return the contained String
+    pub fn into_inner(self) -> String {
+        self.iter.into_inner()
+    }
+
+    /// Returns a string slice of contained `String`
+    pub fn get_inner(&self) -> &str {
+        self.iter.get_inner()
+    }
+
+    /// Returns the part of the contained String that has not been split yet (empty once the
+    /// iterator is exhausted)
+    pub fn as_str(&self) -> &str {
+        self.remainder().unwrap_or("")
+    }
+
+    /// Returns the part of the contained String that has not been split yet, or None if the
+    /// iterator is exhausted (like std's unstable `SplitN::remainder`)
+    pub fn remainder(&self) -> Option<&str> {
+        if self.count == 0 {
+            None
+        } else {
+            self.iter.remainder()
+        }
+    }
+
+    /// Consume this struct and return the part of the contained String that has not been split
+    /// yet, or None if the iterator is exhausted
+    ///
+    /// The existing allocation is reused.
+    pub fn into_remainder(self) -> Option<String> {
+        if self.count == 0 {
+            None
+        } else {
+            self.iter.into_remainder()
+        }
+    }
+
+    /// Like `next`, but borrows the piece from the contained String instead of allocating
+    pub fn next_str(&mut self) -> Option<&str> {
+        match self.count {
+            0 => None,
+            1 => {
+                // the last piece is everything that's left
+                self.count = 0;
+                if self.iter.finished {
+                    return None;
+                }
+                self.iter.finished = true;
+                Some(&self.iter.s[self.iter.start..])
+            }
+            _ => {
+                self.count -= 1;
+                self.iter.next_str()
+            }
+        }
+    }
+}
+
+impl Iterator for OwnedSplitN {
+    type Item = String;
+
+    fn next(&mut self) -> Option<String> {
+        self.next_str().map(str::to_owned)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.count == 0 || self.iter.finished {
+            (0, Some(0))
+        } else {
+            (1, Some(self.count))
+        }
+    }
+}
+
+iter_traits!(OwnedSplitN, String, [FusedIterator]);
+
+#[cfg(feature = "lending")]
+impl LendingIterator for OwnedSplit {
+    type Item<'a> = &'a str;
+
+    fn next(&mut self) -> Option<&str> {
+        self.next_str()
+    }
+}
+
+#[cfg(feature = "lending")]
+impl LendingIterator for OwnedSplitN {
+    type Item<'a> = &'a str;
+
+    fn next(&mut self) -> Option<&str> {
+        self.next_str()
+    }
+}
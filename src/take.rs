@@ -0,0 +1,142 @@
+//! Take and skip adapters
+
+use core::iter::{Iterator, FusedIterator};
+
+/// Iterator adapter that yields at most a given number of chars
+///
+/// Created by the `take_chars` method on the owned iterators. Unlike `Iterator::take`, it has the
+/// accessors of the owned iterators (`as_str`, `peek`, `is_empty` and so on), and they only look at
+/// the text that this adapter will still yield.
+///
+/// # Example
+///
+/// ```rust
+/// # use owned_chars::OwnedCharsExt;
+/// let mut chars = String::from("héllo").into_chars().take_chars(3);
+/// assert_eq!(chars.next(), Some('h'));
+/// assert_eq!(chars.as_str(), "él");
+/// assert_eq!(chars.get_inner(), "héllo");
+/// assert_eq!(chars.by_ref().collect::<String>(), "él");
+/// assert!(chars.is_empty());
+/// assert_eq!(chars.peek(), None);
+/// assert_eq!(chars.into_inner(), "héllo");
+/// ```
+#[derive(Debug, Clone)]
+pub struct TakeChars<I> {
+    pub(crate) iter: I,
+    /// number of chars that may still be yielded
+    n: usize,
+}
+
+/// Iterator adapter that skips a given number of chars before yielding the rest
+///
+/// Created by the `skip_chars` method on the owned iterators. Unlike `Iterator::skip`, it has the
+/// accessors of the owned iterators (`as_str`, `peek`, `byte_offset` and so on), and they only look
+/// at the text that this adapter will still yield. The chars are skipped when the first one is
+/// requested.
+///
+/// # Example
+///
+/// ```rust
+/// # use owned_chars::OwnedCharsExt;
+/// let chars = String::from("héllo").into_char_indices().skip_chars(2);
+/// assert_eq!(chars.as_str(), "llo");
+/// assert_eq!(chars.byte_offset(), 3);
+/// assert_eq!(chars.peek(), Some((3, 'l')));
+/// assert_eq!(chars.collect::<Vec<_>>(), [(3, 'l'), (4, 'l'), (5, 'o')]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct SkipChars<I> {
+    pub(crate) iter: I,
+    /// number of chars that still have to be skipped
+    n: usize,
+}
+
+impl<I> TakeChars<I> {
+    /// Wrap an iterator, allowing at most `n` items to be yielded
+    pub fn new(iter: I, n: usize) -> Self {
+        TakeChars { iter, n }
+    }
+
+    /// Consume the adapter and return the wrapped iterator, which is not limited any more
+    pub fn into_wrapped(self) -> I {
+        self.iter
+    }
+
+    /// Returns the number of chars that may still be yielded
+    pub(crate) fn remaining(&self) -> usize {
+        self.n
+    }
+}
+
+impl<I> SkipChars<I> {
+    /// Wrap an iterator, skipping its first `n` items
+    pub fn new(iter: I, n: usize) -> Self {
+        SkipChars { iter, n }
+    }
+
+    /// Consume the adapter and return the wrapped iterator (the chars that were to be skipped are
+    /// only skipped if iteration has started)
+    pub fn into_wrapped(self) -> I {
+        self.iter
+    }
+
+    /// Returns the number of chars that still have to be skipped
+    pub(crate) fn remaining(&self) -> usize {
+        self.n
+    }
+}
+
+/// Returns the part of `s` after its first `n` chars, or the empty string if it is shorter
+pub(crate) fn skip_str(s: &str, n: usize) -> &str {
+    match s.char_indices().nth(n) {
+        Some((i, _)) => &s[i..],
+        None => "",
+    }
+}
+
+/// Returns the first `n` chars of `s`, or all of it if it is shorter
+pub(crate) fn take_str(s: &str, n: usize) -> &str {
+    match s.char_indices().nth(n) {
+        Some((i, _)) => &s[..i],
+        None => s,
+    }
+}
+
+impl<I: Iterator> Iterator for TakeChars<I> {
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<I::Item> {
+        if self.n == 0 {
+            return None;
+        }
+        self.n -= 1;
+        self.iter.next()
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lower, upper) = self.iter.size_hint();
+        (lower.min(self.n), Some(upper.map_or(self.n, |upper| upper.min(self.n))))
+    }
+}
+
+impl<I: Iterator> Iterator for SkipChars<I> {
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<I::Item> {
+        if self.n > 0 {
+            let n = self.n;
+            self.n = 0;
+            self.iter.nth(n)
+        } else {
+            self.iter.next()
+        }
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lower, upper) = self.iter.size_hint();
+        (lower.saturating_sub(self.n), upper.map(|upper| upper.saturating_sub(self.n)))
+    }
+}
+
+impl<I: FusedIterator> FusedIterator for TakeChars<I> {}
+
+impl<I: FusedIterator> FusedIterator for SkipChars<I> {}
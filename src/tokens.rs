@@ -0,0 +1,174 @@
+//! Simple tokenizer
+//!
+//! `Tokens` splits an owned String into coarse tokens (identifiers, numbers, string literals,
+//! whitespace and punctuation) with their spans. That is enough for many small languages and
+//! config formats; anything more specific can be built on `StrCursor` (see the `cursor` module).
+//!
+//! # Example
+//!
+//! ```rust
+//! use owned_chars::tokens::{Options, TokenKind, Tokens};
+//!
+//! let options = Options { skip_whitespace: true, ..Options::default() };
+//! let mut tokens = Tokens::with_options(String::from(r#"x1 = "a \" b" + 4.5"#), options);
+//! let mut found = vec![];
+//! while let Some(token) = tokens.next() {
+//!     found.push((token.kind, tokens.text(&token).to_owned()));
+//! }
+//! assert_eq!(found, [
+//!     (TokenKind::Ident, String::from("x1")),
+//!     (TokenKind::Punct, String::from("=")),
+//!     (TokenKind::Str { terminated: true }, String::from(r#""a \" b""#)),
+//!     (TokenKind::Punct, String::from("+")),
+//!     (TokenKind::Number, String::from("4.5")),
+//! ]);
+//! ```
+
+use alloc::string::String;
+use core::iter::{Iterator, FusedIterator};
+use core::ops::Range;
+use cursor::StrCursor;
+
+/// Kinds of tokens produced by `Tokens`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TokenKind {
+    /// A letter or one of `Options::ident_extra`, followed by any number of those or digits
+    Ident,
+    /// An ASCII digit followed by any number of alphanumerics, `_`, or `.` before a digit
+    Number,
+    /// Text between a pair of `Options::quotes` (including the quotes)
+    Str {
+        /// false if the input ended before the closing quote
+        terminated: bool,
+    },
+    /// A run of whitespace
+    Whitespace,
+    /// Any other single char
+    Punct,
+}
+
+/// A token: its kind and byte range in the String
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Token {
+    /// What kind of token this is
+    pub kind: TokenKind,
+    /// Where the token is in the tokenized String
+    pub span: Range<usize>,
+}
+
+/// Configuration for `Tokens`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Options {
+    /// Don't yield whitespace tokens (default false)
+    pub skip_whitespace: bool,
+    /// Chars that can appear in identifiers besides alphanumerics (default `_`)
+    pub ident_extra: String,
+    /// Chars that start and end string literals (default `"`)
+    pub quotes: String,
+    /// Char that escapes the next char inside string literals (default `\`)
+    pub escape: Option<char>,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options {
+            skip_whitespace: false,
+            ident_extra: String::from("_"),
+            quotes: String::from("\""),
+            escape: Some('\\'),
+        }
+    }
+}
+
+/// Iterator over the tokens of a string (the string is owned by the iterator; see the module
+/// documentation)
+#[derive(Debug)]
+pub struct Tokens {
+    cursor: StrCursor,
+    options: Options,
+}
+
+impl Tokens {
+    /// Create Self from a String with the default options, moving the String into Self
+    pub fn new(s: String) -> Self {
+        Tokens::with_options(s, Options::default())
+    }
+
+    /// Create Self from a String and options, moving the String into Self
+    pub fn with_options(s: String, options: Options) -> Self {
+        Tokens { cursor: StrCursor::new(s), options }
+    }
+
+    /// Consume this struct and return the contained String
+    pub fn into_inner(self) -> String {
+        self.cursor.into_inner()
+    }
+
+    /// Returns a string slice of contained `String`
+    pub fn get_inner(&self) -> &str {
+        self.cursor.get_inner()
+    }
+
+    /// Returns the part of the contained String that has not been tokenized yet
+    pub fn as_str(&self) -> &str {
+        self.cursor.as_str()
+    }
+
+    /// Returns the text of a token
+    pub fn text(&self, token: &Token) -> &str {
+        self.cursor.slice(token.span.clone())
+    }
+
+    /// Returns true if `c` can continue an identifier
+    fn is_ident_char(&self, c: char) -> bool {
+        c.is_alphanumeric() || self.options.ident_extra.contains(c)
+    }
+}
+
+impl Iterator for Tokens {
+    type Item = Token;
+
+    fn next(&mut self) -> Option<Token> {
+        if self.options.skip_whitespace {
+            self.cursor.skip_whitespace();
+        }
+        let start = self.cursor.mark();
+        let c = self.cursor.bump()?;
+        let kind = if c.is_whitespace() {
+            self.cursor.skip_whitespace();
+            TokenKind::Whitespace
+        } else if c.is_ascii_digit() {
+            loop {
+                match self.cursor.peek() {
+                    Some(c) if c.is_alphanumeric() || c == '_' => {}
+                    Some('.') if self.cursor.peek_nth(1).is_some_and(|c| c.is_ascii_digit()) => {}
+                    _ => break,
+                }
+                self.cursor.bump();
+            }
+            TokenKind::Number
+        } else if c.is_alphabetic() || self.options.ident_extra.contains(c) {
+            while self.cursor.peek().is_some_and(|c| self.is_ident_char(c)) {
+                self.cursor.bump();
+            }
+            TokenKind::Ident
+        } else if self.options.quotes.contains(c) {
+            let terminated = loop {
+                match self.cursor.bump() {
+                    Some(q) if q == c => break true,
+                    Some(e) if Some(e) == self.options.escape => {
+                        self.cursor.bump();
+                    }
+                    Some(_) => {}
+                    None => break false,
+                }
+            };
+            TokenKind::Str { terminated }
+        } else {
+            TokenKind::Punct
+        };
+        Some(Token { kind, span: self.cursor.span_from(start) })
+    }
+}
+
+iter_traits!(Tokens, Token, [FusedIterator]);
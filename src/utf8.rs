@@ -0,0 +1,100 @@
+//! Char iterator over possibly invalid UTF-8
+
+use alloc::vec::Vec;
+use core::iter::{Iterator, FusedIterator};
+use core::str;
+use error::InvalidUtf8;
+
+/// Iterator over the chars of a byte vector holding UTF-8 that may be invalid (the bytes are owned
+/// by the iterator)
+///
+/// Each invalid sequence is reported as an error giving its position and length, following the
+/// same rules as `String::from_utf8_lossy` (which would replace it with one U+FFFD REPLACEMENT
+/// CHARACTER). Iteration continues after an error, so the caller can choose to substitute, skip or
+/// stop.
+///
+/// # Example
+///
+/// ```rust
+/// # use owned_chars::Utf8Chars;
+/// let mut chars = Utf8Chars::from_vec(b"a\xf0\x9f\xff".to_vec());
+/// assert_eq!(chars.next(), Some(Ok('a')));
+/// let err = chars.next().unwrap().unwrap_err();
+/// assert_eq!((err.offset(), err.error_len()), (1, 2));
+/// let err = chars.next().unwrap().unwrap_err();
+/// assert_eq!((err.offset(), err.error_len()), (3, 1));
+/// assert_eq!(chars.next(), None);
+///
+/// let chars = Utf8Chars::from_vec(b"caf\xc3\xa9!\xc3".to_vec());
+/// let valid = chars.filter_map(Result::ok).collect::<String>();
+/// assert_eq!(valid, "café!");
+/// ```
+#[derive(Debug, Clone)]
+pub struct Utf8Chars {
+    v: Vec<u8>,
+    pos: usize,
+}
+
+impl Utf8Chars {
+    /// Create Self from a byte vector, moving the vector into Self
+    pub fn from_vec(v: Vec<u8>) -> Self {
+        Utf8Chars { v, pos: 0 }
+    }
+
+    /// Consume this struct and return the contained vector
+    pub fn into_inner(self) -> Vec<u8> {
+        self.v
+    }
+
+    /// Returns a slice of the contained vector
+    pub fn get_inner(&self) -> &[u8] {
+        &self.v
+    }
+
+    /// Returns the bytes that have not been decoded yet
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.v[self.pos..]
+    }
+
+    /// Returns the number of bytes that have been consumed
+    pub fn byte_offset(&self) -> usize {
+        self.pos
+    }
+}
+
+impl Iterator for Utf8Chars {
+    type Item = Result<char, InvalidUtf8>;
+
+    fn next(&mut self) -> Option<Result<char, InvalidUtf8>> {
+        let start = self.pos;
+        let first = *self.v.get(start)?;
+        if first.is_ascii() {
+            self.pos += 1;
+            return Some(Ok(first as char));
+        }
+
+        // a char is at most 4 bytes long
+        let bytes = &self.v[start..self.v.len().min(start + 4)];
+        let valid = match str::from_utf8(bytes) {
+            Ok(s) => s,
+            // the first char can be valid even if what follows it isn't
+            Err(e) if e.valid_up_to() > 0 => str::from_utf8(&bytes[..e.valid_up_to()]).unwrap(),
+            // error_len is None for a sequence cut short by the end of the input
+            Err(e) => {
+                let len = e.error_len().unwrap_or(bytes.len());
+                self.pos += len;
+                return Some(Err(InvalidUtf8::new(start, len)));
+            }
+        };
+        let c = valid.chars().next().unwrap();
+        self.pos += c.len_utf8();
+        Some(Ok(c))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.v.len() - self.pos;
+        (len.div_ceil(4), Some(len))
+    }
+}
+
+iter_traits!(Utf8Chars, Result<char, InvalidUtf8>, [FusedIterator]);
@@ -0,0 +1,80 @@
+//! Owned iterator over two strings in lockstep
+
+use alloc::string::String;
+use core::iter::{Iterator, FusedIterator};
+
+/// Iterator over the chars of two strings in pairs (the strings are owned by the iterator)
+///
+/// Like zipping two `OwnedChars`, this yields pairs only until the shorter string runs out; the
+/// chars of the longer string past that point are never yielded. They are not lost, though:
+/// `as_strs` returns the unpaired rest of both strings, which makes it easy to tell where two
+/// strings start to differ.
+///
+/// # Example
+///
+/// ```rust
+/// # use owned_chars::OwnedZipChars;
+/// let mut zip = OwnedZipChars::new(String::from("héllo"), String::from("hèlp!"));
+/// let same = zip.by_ref().take_while(|(a, b)| a == b).count();
+/// assert_eq!(same, 1);
+/// assert_eq!(zip.as_strs(), ("llo", "lp!"));
+///
+/// let mut zip = OwnedZipChars::new(String::from("ab"), String::from("abc"));
+/// assert_eq!(zip.next(), Some(('a', 'a')));
+/// assert_eq!(zip.next(), Some(('b', 'b')));
+/// assert_eq!(zip.next(), None);
+/// assert_eq!(zip.as_strs(), ("", "c"));
+/// ```
+#[derive(Debug, Clone)]
+pub struct OwnedZipChars {
+    a: String,
+    b: String,
+    /// byte positions of the next chars of `a` and `b`
+    pos: (usize, usize),
+}
+
+impl OwnedZipChars {
+    /// Create Self from two Strings, moving them into Self
+    pub fn new(a: String, b: String) -> Self {
+        OwnedZipChars { a, b, pos: (0, 0) }
+    }
+
+    /// Consume this struct and return the contained Strings
+    pub fn into_inner(self) -> (String, String) {
+        (self.a, self.b)
+    }
+
+    /// Returns string slices of the contained Strings
+    pub fn get_inner(&self) -> (&str, &str) {
+        (&self.a, &self.b)
+    }
+
+    /// Returns the parts of the contained Strings whose chars have not been yielded yet
+    pub fn as_strs(&self) -> (&str, &str) {
+        (&self.a[self.pos.0..], &self.b[self.pos.1..])
+    }
+
+    /// Returns the byte positions of the next chars in the contained Strings
+    pub fn byte_offsets(&self) -> (usize, usize) {
+        self.pos
+    }
+}
+
+impl Iterator for OwnedZipChars {
+    type Item = (char, char);
+
+    fn next(&mut self) -> Option<(char, char)> {
+        let (a, b) = self.as_strs();
+        let pair = (a.chars().next()?, b.chars().next()?);
+        self.pos.0 += pair.0.len_utf8();
+        self.pos.1 += pair.1.len_utf8();
+        Some(pair)
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (a, b) = self.as_strs();
+        let (a, b) = (a.chars().size_hint(), b.chars().size_hint());
+        (a.0.min(b.0), Some(a.1.unwrap_or(0).min(b.1.unwrap_or(0))))
+    }
+}
+
+iter_traits!(OwnedZipChars, (char, char), [FusedIterator]);